@@ -0,0 +1,193 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use advent_of_code_2022_rust::day01::Day01;
+use advent_of_code_2022_rust::day02::Day02;
+use advent_of_code_2022_rust::day03::Day03;
+use advent_of_code_2022_rust::day04::Day04;
+use advent_of_code_2022_rust::day05::Day05;
+use advent_of_code_2022_rust::day06::Day06;
+use advent_of_code_2022_rust::Solution;
+
+// Number of lines (or characters for day 6) in the synthetic inputs.
+const SCALE: usize = 100_000;
+
+fn bench_day<S: Solution>(c: &mut Criterion, inputs: &[(&str, String)]) {
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+
+    for (name, input) in inputs {
+        group.bench_with_input(BenchmarkId::new("parse", name), input, |b, input| {
+            b.iter(|| S::parse(black_box(input)).unwrap())
+        });
+
+        let parsed = S::parse(input).unwrap();
+        group.bench_with_input(BenchmarkId::new("part1", name), &parsed, |b, parsed| {
+            b.iter(|| S::part1(black_box(parsed)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", name), &parsed, |b, parsed| {
+            b.iter(|| S::part2(black_box(parsed)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+macro_rules! inputs {
+    ($day:literal, $synthetic:expr) => {
+        [
+            (
+                "sample",
+                include_str!(concat!("../src/", $day, "/sample.txt")).to_string(),
+            ),
+            (
+                "data",
+                include_str!(concat!("../src/", $day, "/data.txt")).to_string(),
+            ),
+            ("synthetic", $synthetic),
+        ]
+    };
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<Day01>(c, &inputs!("day01", synthetic::day01(SCALE)));
+    bench_day::<Day02>(c, &inputs!("day02", synthetic::day02(SCALE)));
+    bench_day::<Day03>(c, &inputs!("day03", synthetic::day03(SCALE)));
+    bench_day::<Day04>(c, &inputs!("day04", synthetic::day04(SCALE)));
+    bench_day::<Day05>(c, &inputs!("day05", synthetic::day05(SCALE)));
+    bench_day::<Day06>(c, &inputs!("day06", synthetic::day06(SCALE)));
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
+
+// Generators for large, valid inputs. They use a fixed-seed xorshift so every run
+// benchmarks exactly the same input.
+mod synthetic {
+    struct Rng(u64);
+
+    impl Rng {
+        fn new() -> Self {
+            Rng(0x2022_1201)
+        }
+
+        fn next(&mut self, max: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % max as u64) as usize
+        }
+
+        fn between(&mut self, min: usize, max: usize) -> usize {
+            min + self.next(max - min + 1)
+        }
+    }
+
+    const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    pub fn day01(lines: usize) -> String {
+        let mut rng = Rng::new();
+        let mut elves = vec![];
+        let mut count = 0;
+        while count < lines {
+            let items = rng.between(1, 15);
+            let elf: Vec<String> = (0..items)
+                .map(|_| rng.between(1000, 60000).to_string())
+                .collect();
+            elves.push(elf.join("\n"));
+            count += items + 1;
+        }
+        elves.join("\n\n")
+    }
+
+    pub fn day02(lines: usize) -> String {
+        let mut rng = Rng::new();
+        let rounds: Vec<String> = (0..lines)
+            .map(|_| {
+                let a = (b'A' + rng.next(3) as u8) as char;
+                let b = (b'X' + rng.next(3) as u8) as char;
+                format!("{} {}", a, b)
+            })
+            .collect();
+        rounds.join("\n")
+    }
+
+    pub fn day03(lines: usize) -> String {
+        let mut rng = Rng::new();
+        let mut rucksacks = vec![];
+        for _ in 0..lines / 3 {
+            let badge = ITEMS[rng.next(ITEMS.len())];
+            for _ in 0..3 {
+                let half = rng.between(8, 24);
+                let shared = ITEMS[rng.next(ITEMS.len())];
+                let mut left: Vec<u8> = (0..half).map(|_| ITEMS[rng.next(ITEMS.len())]).collect();
+                let mut right: Vec<u8> = (0..half).map(|_| ITEMS[rng.next(ITEMS.len())]).collect();
+                left[0] = shared;
+                right[half - 1] = shared;
+                left[half / 2] = badge;
+                rucksacks.push(format!(
+                    "{}{}",
+                    String::from_utf8(left).unwrap(),
+                    String::from_utf8(right).unwrap()
+                ));
+            }
+        }
+        rucksacks.join("\n")
+    }
+
+    pub fn day04(lines: usize) -> String {
+        let mut rng = Rng::new();
+        let pairs: Vec<String> = (0..lines)
+            .map(|_| {
+                let a = rng.between(1, 99);
+                let b = rng.between(a, 99);
+                let c = rng.between(1, 99);
+                let d = rng.between(c, 99);
+                format!("{}-{},{}-{}", a, b, c, d)
+            })
+            .collect();
+        pairs.join("\n")
+    }
+
+    pub fn day05(commands: usize) -> String {
+        const STACKS: usize = 9;
+        const HEIGHT: usize = 50;
+
+        let mut rng = Rng::new();
+        let mut heights = [HEIGHT; STACKS];
+
+        let mut lines = vec![];
+        for _ in 0..HEIGHT {
+            let row: Vec<String> = (0..STACKS)
+                .map(|_| format!("[{}]", ITEMS[26 + rng.next(26)] as char))
+                .collect();
+            lines.push(row.join(" "));
+        }
+        let index: Vec<String> = (1..=STACKS).map(|i| format!(" {} ", i)).collect();
+        lines.push(index.join(" "));
+        lines.push(String::new());
+
+        for _ in 0..commands {
+            let from = loop {
+                let from = rng.next(STACKS);
+                if heights[from] > 0 {
+                    break from;
+                }
+            };
+            let to = (from + rng.between(1, STACKS - 1)) % STACKS;
+            let size = rng.between(1, heights[from].min(10));
+            heights[from] -= size;
+            heights[to] += size;
+            lines.push(format!("move {} from {} to {}", size, from + 1, to + 1));
+        }
+
+        lines.join("\n")
+    }
+
+    // Only 13 distinct letters until the very end, so no message marker is found
+    // before the whole signal has been scanned.
+    pub fn day06(chars: usize) -> String {
+        let mut rng = Rng::new();
+        let mut signal: Vec<u8> = (0..chars).map(|_| ITEMS[rng.next(13)]).collect();
+        signal.extend_from_slice(&ITEMS[..26]);
+        String::from_utf8(signal).unwrap()
+    }
+}
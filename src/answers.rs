@@ -0,0 +1,153 @@
+//! Known answers, used to catch refactors that change a result.
+//!
+//! Answers are kept in a TOML file with one table per day:
+//!
+//! ```toml
+//! [day01]
+//! part1 = 69626
+//! part2 = 206780
+//! ```
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Answer, Report};
+
+pub const ANSWERS_ENV: &str = "AOC_ANSWERS";
+pub const DEFAULT_ANSWERS: &str = "answers.toml";
+
+/// The answers file: `$AOC_ANSWERS`, or `./answers.toml`.
+pub fn answers_path() -> PathBuf {
+    env::var_os(ANSWERS_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_ANSWERS))
+}
+
+#[derive(Debug, Default)]
+pub struct Answers(HashMap<(u8, u8), Answer>);
+
+/// The result of checking an answer against the known one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: Answer },
+    Unknown,
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let table: toml::Table = input.parse().map_err(|e| format!("{}", e))?;
+
+        let mut answers = HashMap::new();
+        for (key, parts) in table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or(format!("Invalid day `{}`, expected e.g. `day01`", key))?;
+            let parts = parts
+                .as_table()
+                .ok_or(format!("`{}` must be a table", key))?;
+
+            for (key, value) in parts {
+                let part = match key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => {
+                        return Err(format!(
+                            "Invalid part `{}`, expected `part1` or `part2`",
+                            key
+                        ))
+                    }
+                };
+                let answer = match value {
+                    toml::Value::Integer(n) => Answer::Number(*n),
+                    toml::Value::String(s) => Answer::Text(s.clone()),
+                    _ => {
+                        return Err(format!(
+                            "Answer for day {} part {} must be a number or string",
+                            day, part
+                        ))
+                    }
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(Answers(answers))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let input = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Answers::parse(&input).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Loads the answers file if it exists; a missing file means no known answers.
+    pub fn load_or_default(path: &Path) -> Result<Self, String> {
+        if path.exists() {
+            Answers::load(path)
+        } else {
+            Ok(Answers::default())
+        }
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.0.get(&(day, part))
+    }
+
+    pub fn verify(&self, day: u8, part: u8, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.clone(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// The parts of `report` whose answer differs from the known one, with the expected answer.
+    pub fn mismatches(&self, report: &Report) -> Vec<(u8, Answer, Answer)> {
+        report
+            .parts
+            .iter()
+            .filter_map(|p| match self.verify(report.day, p.part, &p.answer) {
+                Verdict::Wrong { expected } => Some((p.part, p.answer.clone(), expected)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_answers() {
+        let answers =
+            Answers::parse("[day01]\npart1 = 24000\n\n[day05]\npart2 = \"MCD\"\n").unwrap();
+
+        assert_eq!(
+            answers.verify(1, 1, &Answer::Number(24000)),
+            Verdict::Correct
+        );
+        assert_eq!(
+            answers.verify(5, 2, &Answer::Text("CMZ".to_string())),
+            Verdict::Wrong {
+                expected: Answer::Text("MCD".to_string())
+            }
+        );
+        assert_eq!(
+            answers.verify(1, 2, &Answer::Number(45000)),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(Answers::parse("[one]\npart1 = 1\n").is_err());
+        assert!(Answers::parse("[day01]\npart3 = 1\n").is_err());
+        assert!(Answers::parse("[day01]\npart1 = 1.5\n").is_err());
+    }
+}
@@ -0,0 +1,204 @@
+//! Command line handling shared by the `dayNN` binaries.
+//!
+//! Usage: `dayNN [<file>|-] [--part 1|2] [--inputs <dir>] [--json]`
+
+use std::env;
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::process;
+
+use serde_json::json;
+
+use crate::{find_day, input, Answer, Report, Solution};
+
+/// The arguments of a day binary, from which day-specific options can be taken
+/// before the common ones are parsed with [`Args::options`].
+pub struct Args(Vec<String>);
+
+/// The options understood by every day binary.
+pub struct Options {
+    pub path: Option<String>,
+    pub part: Option<u8>,
+    pub inputs_dir: PathBuf,
+    pub json: bool,
+}
+
+impl Args {
+    pub fn from_env() -> Self {
+        Args(env::args().skip(1).collect())
+    }
+
+    /// Removes `name` from the arguments, returning whether it was present.
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.0.iter().position(|a| a == name) {
+            Some(i) => {
+                self.0.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes `name` and the value following it from the arguments.
+    pub fn value(&mut self, name: &str) -> Result<Option<String>, String> {
+        match self.0.iter().position(|a| a == name) {
+            Some(i) if i + 1 < self.0.len() => {
+                let value = self.0.remove(i + 1);
+                self.0.remove(i);
+                Ok(Some(value))
+            }
+            Some(_) => Err(format!("{} requires a value", name)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn options(mut self) -> Result<Options, String> {
+        let json = self.flag("--json");
+        let part = match self.value("--part")?.as_deref() {
+            Some("1") => Some(1),
+            Some("2") => Some(2),
+            Some(part) => return Err(format!("Invalid part: {}", part)),
+            None => None,
+        };
+        let inputs_dir = self
+            .value("--inputs")?
+            .map(PathBuf::from)
+            .unwrap_or_else(input::inputs_dir);
+
+        let mut rest = self.0.into_iter();
+        let path = rest.next();
+        if let Some(arg) = rest.next() {
+            return Err(format!("Unexpected argument: {}", arg));
+        }
+        if let Some(path) = path.as_deref().filter(|p| p.starts_with("--")) {
+            return Err(format!("Unknown option: {}", path));
+        }
+
+        Ok(Options {
+            path,
+            part,
+            inputs_dir,
+            json,
+        })
+    }
+}
+
+impl Options {
+    pub fn read_input(&self, day: u8) -> Result<String, String> {
+        let embedded = find_day(day).and_then(|d| d.embedded);
+        input::read(day, self.path.as_deref(), &self.inputs_dir, embedded)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Opens the input for streaming instead of reading it all at once.
+    pub fn open_input(&self, day: u8) -> Result<Box<dyn BufRead>, String> {
+        let embedded = find_day(day).and_then(|d| d.embedded);
+        input::open(day, self.path.as_deref(), &self.inputs_dir, embedded)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Runs a day binary: reads the input, solves the requested parts and prints
+/// the answers, labelled with `labels`, or as JSON.
+pub fn run<S: Solution>(labels: [&str; 2]) {
+    let result = Args::from_env().options().and_then(|options| {
+        let input = options.read_input(S::DAY)?;
+        let report =
+            crate::solution::solve::<S>(&input, options.part).map_err(|e| e.to_string())?;
+        print_report(&report, &options, labels);
+        Ok(())
+    });
+
+    exit_on_error(result);
+}
+
+pub fn print_report(report: &Report, options: &Options, labels: [&str; 2]) {
+    if options.json {
+        for line in json_lines(report) {
+            println!("{}", line);
+        }
+        return;
+    }
+
+    println!("Parsed input in {:.2?}", report.parse_time);
+    for part in &report.parts {
+        let label = labels[part.part as usize - 1];
+        println!("{}: {} (in {:.2?})", label, part.answer, part.elapsed);
+    }
+}
+
+/// One JSON object per solved part, e.g.
+/// `{"day":1,"part":1,"answer":24000,"elapsed_ns":1200,"parse_ns":3400}`.
+pub fn json_lines(report: &Report) -> Vec<String> {
+    report
+        .parts
+        .iter()
+        .map(|part| {
+            json!({
+                "day": report.day,
+                "part": part.part,
+                "answer": json_answer(&part.answer),
+                "elapsed_ns": part.elapsed.as_nanos() as u64,
+                "parse_ns": report.parse_time.as_nanos() as u64,
+            })
+            .to_string()
+        })
+        .collect()
+}
+
+pub fn json_answer(answer: &Answer) -> serde_json::Value {
+    match answer {
+        Answer::Number(n) => json!(n),
+        Answer::Text(s) => json!(s),
+    }
+}
+
+pub fn exit_on_error<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::solution::PartReport;
+
+    fn args(args: &[&str]) -> Args {
+        Args(args.iter().map(|a| a.to_string()).collect())
+    }
+
+    #[test]
+    fn parses_options() {
+        let mut args = args(&["--top", "5", "data.txt", "--json", "--part", "2"]);
+        assert_eq!(args.value("--top"), Ok(Some("5".to_string())));
+
+        let options = args.options().unwrap();
+        assert_eq!(options.path.as_deref(), Some("data.txt"));
+        assert_eq!(options.part, Some(2));
+        assert!(options.json);
+
+        assert!(self::args(&["--top", "5"]).options().is_err());
+    }
+
+    #[test]
+    fn formats_json_lines() {
+        let report = Report {
+            day: 5,
+            parse_time: Duration::from_nanos(100),
+            parts: vec![PartReport {
+                part: 1,
+                answer: Answer::Text("CMZ".to_string()),
+                elapsed: Duration::from_nanos(42),
+            }],
+        };
+
+        assert_eq!(
+            json_lines(&report),
+            vec![r#"{"day":5,"part":1,"answer":"CMZ","elapsed_ns":42,"parse_ns":100}"#]
+        );
+    }
+}
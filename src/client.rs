@@ -0,0 +1,135 @@
+//! Talking to the Advent of Code website.
+//!
+//! The session token is read from `$AOC_SESSION` or the `.aoc-session` file, and the
+//! base URL can be overridden with `$AOC_BASE_URL` (e.g. to point at a local server).
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{input, Answer};
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const SESSION_FILE: &str = ".aoc-session";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2022;
+
+const USER_AGENT: &str = "github.com/davideimola/advent-of-code-2022-rust";
+
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+/// Where a fetched input came from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fetched {
+    Downloaded,
+    Cached,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: Option<String>) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// A client configured from the environment, see the module documentation.
+    pub fn from_env() -> Self {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Client::new(&base_url, session_from_env())
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    fn session(&self) -> Result<&str, String> {
+        self.session.as_deref().ok_or(format!(
+            "No session token: set {} or write it to {}",
+            SESSION_ENV, SESSION_FILE
+        ))
+    }
+
+    fn cookie(&self) -> Result<String, String> {
+        Ok(format!("session={}", self.session()?))
+    }
+
+    /// Downloads a day's input, without looking at the cache.
+    pub fn download_input(&self, day: u8) -> Result<String, String> {
+        let url = format!("{}/input", self.day_url(day));
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie()?)
+            .call()
+            .map_err(|e| request_error(&url, e))?;
+
+        response
+            .into_string()
+            .map_err(|e| format!("{}: {}", url, e))
+    }
+
+    /// Posts an answer and returns the HTML of the response page.
+    pub fn post_answer(&self, day: u8, part: u8, answer: &Answer) -> Result<String, String> {
+        let url = format!("{}/answer", self.day_url(day));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie()?)
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ])
+            .map_err(|e| request_error(&url, e))?;
+
+        response
+            .into_string()
+            .map_err(|e| format!("{}: {}", url, e))
+    }
+
+    /// Returns the path of the cached input, downloading it first if it is not cached yet.
+    pub fn fetch_input(&self, inputs_dir: &Path, day: u8) -> Result<(PathBuf, Fetched), String> {
+        let path = input::input_path(inputs_dir, day);
+        if path.exists() {
+            return Ok((path, Fetched::Cached));
+        }
+
+        let input = self.download_input(day)?;
+
+        fs::create_dir_all(inputs_dir).map_err(|e| format!("{}: {}", inputs_dir.display(), e))?;
+        // Write to a temporary file first so an interrupted download never looks cached.
+        let tmp = path.with_extension("txt.part");
+        fs::write(&tmp, input).map_err(|e| format!("{}: {}", tmp.display(), e))?;
+        fs::rename(&tmp, &path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        Ok((path, Fetched::Downloaded))
+    }
+}
+
+fn session_from_env() -> Option<String> {
+    env::var(SESSION_ENV)
+        .ok()
+        .or_else(|| fs::read_to_string(SESSION_FILE).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn request_error(url: &str, error: ureq::Error) -> String {
+    match error {
+        ureq::Error::Status(404, _) => format!("{}: not found, is the puzzle unlocked yet?", url),
+        ureq::Error::Status(400, _) => {
+            format!("{}: bad request, is the session token still valid?", url)
+        }
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            format!("{}: HTTP {}: {}", url, code, body.trim())
+        }
+        ureq::Error::Transport(e) => e.to_string(),
+    }
+}
@@ -0,0 +1,50 @@
+//! The numeric types calories can be counted in.
+
+use std::fmt;
+
+use nom::character::complete;
+use nom::error::{Error, ErrorKind};
+use nom::IResult;
+
+/// An unsigned integer type for calories, with overflow-checked arithmetic.
+///
+/// Implemented for `u32`, `u64` and `u128`: pick a wider type when totals may not fit.
+pub trait Calories: Copy + Ord + fmt::Debug + fmt::Display + Into<u128> {
+    const ZERO: Self;
+
+    /// Parses a number, failing with [`ErrorKind::TooLarge`] if it does not fit in `Self`.
+    fn parse(s: &str) -> IResult<&str, Self>;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// The sum of `values`, or `None` if it does not fit in `Self`.
+    fn checked_sum(values: impl IntoIterator<Item = Self>) -> Option<Self> {
+        values.into_iter().try_fold(Self::ZERO, Self::checked_add)
+    }
+}
+
+macro_rules! calories {
+    ($($t:ident),*) => {
+        $(
+            impl Calories for $t {
+                const ZERO: Self = 0;
+
+                fn parse(s: &str) -> IResult<&str, Self> {
+                    match complete::$t(s) {
+                        // The digits are there, so the number is too large.
+                        Err(nom::Err::Error(_)) if s.starts_with(|c: char| c.is_ascii_digit()) => {
+                            Err(nom::Err::Failure(Error::new(s, ErrorKind::TooLarge)))
+                        }
+                        result => result,
+                    }
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+calories!(u32, u64, u128);
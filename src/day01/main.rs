@@ -1,84 +1,120 @@
-use std::cmp::Ordering;
-
-use nom::character::complete;
-use nom::character::complete::newline;
-use nom::IResult;
-use nom::multi::separated_list1;
-use nom::sequence::tuple;
-
+use std::time::Instant;
+
+use advent_of_code_2022_rust::cli::{self, Args};
+use advent_of_code_2022_rust::day01::report::{CalorieReport, DEFAULT_BUCKETS};
+use advent_of_code_2022_rust::day01::{self, Calories, Day01, Mode};
+use advent_of_code_2022_rust::{solution, Solution};
+use serde_json::json;
+
+const LABELS: [&str; 2] = ["Max calories", "Sum of top 3 calories"];
+
+/// Usage: `day01 [--top K] [--stream] [--stats [--buckets N]] [--strict]`, plus the options
+/// of every day binary (see [`cli`]).
+///
+/// `--top K` also lists the K elves carrying the most calories. Without `--part`, part 2 is left
+/// out, with a note on stderr, when there are fewer than the 3 elves it needs.
+/// `--stream` reads the input one line at a time instead of loading it, counting in `u64`, and only reports the max and the
+/// top K (3 by default).
+/// `--stats` prints a [`CalorieReport`] instead of the answers.
+///
+/// The input may have CRLF line endings, extra blank lines, padded numbers and `#` comments,
+/// unless `--strict` is given to validate that it follows the puzzle format exactly.
 fn main() {
-    let input = include_str!("./data.txt");
-    let (res, elves) = parse_elves(input).unwrap();
-    assert_eq!(res, "");
-
-    let result = find_elf_with_max_calories(&elves).unwrap();
-    println!("Max calories: {}", result.total_calories());
-
-    println!("Sum of top 3 calories: {}", find_top_3_max_calories(&elves).unwrap());
+    let mut args = Args::from_env();
+    let stream = args.flag("--stream");
+    let stats = args.flag("--stats");
+    let mode = if args.flag("--strict") {
+        Mode::Strict
+    } else {
+        Mode::Tolerant
+    };
+    let result = (|| {
+        let top = positive(args.value("--top")?, "--top")?;
+        let buckets = positive(args.value("--buckets")?, "--buckets")?;
+        let options = args.options()?;
+
+        if stats {
+            let input = options.read_input(Day01::DAY)?;
+            let elves: Vec<day01::Elf> =
+                day01::parse_input(&input, mode).map_err(|e| e.to_string())?;
+            let report = CalorieReport::new(&elves, buckets.unwrap_or(DEFAULT_BUCKETS))
+                .ok_or("no elves in the input")?;
+            if options.json {
+                println!("{}", report.to_json());
+            } else {
+                println!("{}", report);
+            }
+            return Ok(());
+        }
+
+        if stream {
+            let k = top.unwrap_or(3);
+            let reader = options.open_input(Day01::DAY)?;
+            let top = day01::top_k_from_reader::<u64>(reader, mode, k)
+                .map_err(|e| e.to_string())?
+                .ok_or(format!("fewer than {} elves in the input", k))?;
+            if !options.json {
+                println!("{}: {}", LABELS[0], top[0].1);
+            }
+            print_top(&top, options.json)?;
+            return Ok(());
+        }
+
+        let input = options.read_input(Day01::DAY)?;
+        let start = Instant::now();
+        let elves = day01::parse_input(&input, mode).map_err(|e| e.to_string())?;
+        let part = match options.part {
+            None if elves.len() < 3 => {
+                eprintln!("{}: skipped, fewer than 3 elves in the input", LABELS[1]);
+                Some(1)
+            }
+            part => part,
+        };
+        let report = solution::solve_parsed::<Day01>(&elves, start.elapsed(), part)
+            .map_err(|e| e.to_string())?;
+        cli::print_report(&report, &options, LABELS);
+
+        if let Some(k) = top {
+            let top: Vec<(usize, u64)> = day01::top_k(&elves, k)
+                .ok_or(format!("fewer than {} elves in the input", k))?
+                .into_iter()
+                .map(|(i, elf)| (i, elf.total_calories().into()))
+                .collect();
+            print_top(&top, options.json)?;
+        }
+        Ok(())
+    })();
+
+    cli::exit_on_error(result);
 }
 
-fn find_elf_with_max_calories(elves: &[Elf]) -> Option<&Elf> {
-    elves.iter().max_by(|a, b| a.cmp(b))
+fn positive(value: Option<String>, name: &str) -> Result<Option<usize>, String> {
+    value
+        .map(|v| match v.parse() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(format!("Invalid {}: {}", name, v)),
+        })
+        .transpose()
 }
 
-fn find_top_3_max_calories(elves: &[Elf]) -> Option<u32> {
-    let mut c = elves.to_vec();
-    c.sort_by(|a, b| b.cmp(a));
-
-    let res = c[0..3].iter().map(|e| e.total_calories()).sum();
-    Some(res)
-}
-
-#[derive(Debug, Clone)]
-struct Elf {
-    calories: Vec<u32>,
-}
-
-impl Elf {
-    fn parse(s: &str) -> IResult<&str, Self> {
-        let (s, calories) = separated_list1(newline, Elf::parse_calories)(s)?;
-        Ok((s, Elf { calories }))
-    }
-
-    fn parse_calories(s: &str) -> IResult<&str, u32> {
-        complete::u32(s)
+// Elves are numbered from 1, in input order.
+fn print_top(top: &[(usize, u64)], json: bool) -> Result<(), String> {
+    let total = u64::checked_sum(top.iter().map(|(_, calories)| *calories))
+        .ok_or(format!("the sum of the top {} elves overflows", top.len()))?;
+
+    if json {
+        let elves: Vec<_> = top
+            .iter()
+            .map(|(i, calories)| json!({"elf": i + 1, "calories": calories}))
+            .collect();
+        println!("{}", json!({"day": 1, "top": elves, "total": total}));
+        return Ok(());
     }
 
-    fn total_calories(&self) -> u32 {
-        self.calories.iter().sum()
+    println!("Top {} elves:", top.len());
+    for (i, calories) in top {
+        println!("  Elf {}: {}", i + 1, calories);
     }
-
-    pub fn cmp(&self, other: &Self) -> Ordering {
-        self.total_calories().cmp(&other.total_calories())
-    }
-}
-
-fn parse_elves(s: &str) -> IResult<&str, Vec<Elf>> {
-    let double_new_line = tuple((newline, newline));
-    separated_list1(double_new_line, Elf::parse)(s)
+    println!("Sum of top {} calories: {}", top.len(), total);
+    Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn top_elf() {
-        let input = include_str!("./sample.txt");
-        let (res, elves) = parse_elves(input).unwrap();
-        assert_eq!(res, "");
-
-        let result = find_elf_with_max_calories(&elves).unwrap();
-        assert_eq!(result.total_calories(), 24000);
-    }
-
-    #[test]
-    fn top_3_elves() {
-        let input = include_str!("./sample.txt");
-        let (res, elves) = parse_elves(input).unwrap();
-        assert_eq!(res, "");
-
-        let result = find_top_3_max_calories(&elves).unwrap();
-        assert_eq!(result, 45000);
-    }
-}
\ No newline at end of file
@@ -0,0 +1,283 @@
+//! Day 1: Calorie Counting.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use nom::branch::alt;
+use nom::character::complete::{char, line_ending, newline, not_line_ending, space0};
+use nom::combinator::{eof, opt};
+use nom::error::ErrorKind;
+use nom::IResult;
+use nom::multi::{many0, many1, separated_list1};
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
+
+use crate::error::parse_all;
+use crate::{Answer, Error, Result, Solution};
+
+mod calories;
+pub mod report;
+mod stream;
+
+pub use calories::Calories;
+pub use report::CalorieReport;
+pub use stream::{top_k_from_reader, ElfTotals};
+
+/// Finds the elves carrying the most calories.
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<Elf>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input, Mode::Tolerant)
+    }
+
+    fn part1(elves: &Self::Input) -> Result<Answer> {
+        let result = find_elf_with_max_calories(elves)
+            .ok_or_else(|| Error::NoSolution("no elves in the input".to_string()))?;
+        Ok(result.total_calories().into())
+    }
+
+    fn part2(elves: &Self::Input) -> Result<Answer> {
+        Ok(find_top_3_max_calories(elves)?.into())
+    }
+}
+
+/// How strictly the input format is checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Exactly the puzzle format: `\n` line endings and elves separated by one blank line.
+    Strict,
+    /// Also accepts CRLF line endings, runs of blank lines, numbers padded with spaces or
+    /// tabs, and `#` comments, on their own line or after a number.
+    #[default]
+    Tolerant,
+}
+
+/// Parses the whole input with [`parse_elves`] or [`parse_elves_tolerant`].
+pub fn parse_input<C: Calories>(input: &str, mode: Mode) -> Result<Vec<Elf<C>>> {
+    match mode {
+        Mode::Strict => parse_all(input, parse_elves),
+        Mode::Tolerant => parse_all(input, parse_elves_tolerant),
+    }
+}
+
+/// Returns the elf carrying the most calories, or `None` if there are no elves.
+pub fn find_elf_with_max_calories<C: Calories>(elves: &[Elf<C>]) -> Option<&Elf<C>> {
+    elves.iter().max_by(|a, b| a.cmp(b))
+}
+
+/// Returns the calories carried by the top three elves.
+///
+/// Fails if there are fewer than three elves, or if their sum does not fit in `C`.
+pub fn find_top_3_max_calories<C: Calories>(elves: &[Elf<C>]) -> Result<C> {
+    let top = top_k(elves, 3)
+        .ok_or_else(|| Error::NoSolution("fewer than 3 elves in the input".to_string()))?;
+    C::checked_sum(top.iter().map(|(_, e)| e.total_calories()))
+        .ok_or_else(|| Error::Overflow("sum of the top 3 elves".to_string()))
+}
+
+/// Returns the `k` elves carrying the most calories with their index in `elves`, heaviest
+/// first, or `None` if there are fewer than `k` elves. Ties keep the input order.
+pub fn top_k<C: Calories>(elves: &[Elf<C>], k: usize) -> Option<Vec<(usize, &Elf<C>)>> {
+    let top = top_k_totals(elves.iter().map(Elf::total_calories), k)?;
+    Some(top.into_iter().map(|(i, _)| (i, &elves[i])).collect())
+}
+
+/// Returns the `k` largest totals with their index, largest first, or `None` if there are
+/// fewer than `k`. Runs in O(n log k) and only keeps `k` totals in memory.
+pub fn top_k_totals<T: Ord>(
+    totals: impl IntoIterator<Item = T>,
+    k: usize,
+) -> Option<Vec<(usize, T)>> {
+    // A min-heap of the best totals so far, whose root is the next one to be evicted:
+    // the smallest total, and the latest elf among equal totals.
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    let mut count = 0;
+    for (i, total) in totals.into_iter().enumerate() {
+        count += 1;
+        heap.push(Reverse((total, Reverse(i))));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    if count < k {
+        return None;
+    }
+
+    let top = heap.into_sorted_vec().into_iter();
+    Some(top.map(|Reverse((total, Reverse(i)))| (i, total)).collect())
+}
+
+/// The food items carried by a single elf, whose total is known to fit in `C`.
+#[derive(Debug, Clone)]
+pub struct Elf<C = u32> {
+    calories: Vec<C>,
+    total: C,
+}
+
+impl<C: Calories> Elf<C> {
+    /// Parses one calorie entry per line.
+    ///
+    /// Fails with [`ErrorKind::TooLarge`] if an entry or the total does not fit in `C`,
+    /// which [`parse_all`] reports as [`Error::Overflow`].
+    pub fn parse(s: &str) -> IResult<&str, Self> {
+        let (rest, calories) = separated_list1(newline, C::parse)(s)?;
+        Elf::with_total(s, rest, calories)
+    }
+
+    /// Like [`Elf::parse`], in [`Mode::Tolerant`]. Consumes the line ending of the last entry.
+    pub fn parse_tolerant(s: &str) -> IResult<&str, Self> {
+        let entry = terminated(
+            delimited(space0, C::parse, space0),
+            pair(opt(comment), alt((line_ending, eof))),
+        );
+        let (rest, calories) = many1(preceded(many0(comment_line), entry))(s)?;
+        Elf::with_total(s, rest, calories)
+    }
+
+    fn with_total<'a>(s: &'a str, rest: &'a str, calories: Vec<C>) -> IResult<&'a str, Self> {
+        match C::checked_sum(calories.iter().copied()) {
+            Some(total) => Ok((rest, Elf { calories, total })),
+            None => Err(nom::Err::Failure(nom::error::Error::new(
+                s,
+                ErrorKind::TooLarge,
+            ))),
+        }
+    }
+
+    /// The calories of every food item carried, in input order.
+    pub fn calories(&self) -> &[C] {
+        &self.calories
+    }
+
+    /// The sum of all the calories carried.
+    pub fn total_calories(&self) -> C {
+        self.total
+    }
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_calories().cmp(&other.total_calories())
+    }
+}
+
+/// Parses the inventory of every elf, separated by blank lines.
+pub fn parse_elves<C: Calories>(s: &str) -> IResult<&str, Vec<Elf<C>>> {
+    let double_new_line = tuple((newline, newline));
+    separated_list1(double_new_line, Elf::parse)(s)
+}
+
+/// Parses the inventory of every elf in [`Mode::Tolerant`]: elves are separated by any
+/// number of blank lines, and comment lines are skipped wherever they are.
+pub fn parse_elves_tolerant<C: Calories>(s: &str) -> IResult<&str, Vec<Elf<C>>> {
+    let (s, elves) = many0(preceded(many0(ignored_line), Elf::parse_tolerant))(s)?;
+    let (s, _) = many0(ignored_line)(s)?;
+    // The last line may be blank or a comment without a line ending.
+    let (s, _) = pair(space0, opt(comment))(s)?;
+    Ok((s, elves))
+}
+
+fn comment(s: &str) -> IResult<&str, &str> {
+    preceded(char('#'), not_line_ending)(s)
+}
+
+fn comment_line(s: &str) -> IResult<&str, &str> {
+    terminated(preceded(space0, comment), line_ending)(s)
+}
+
+// A blank or comment line.
+fn ignored_line(s: &str) -> IResult<&str, Option<&str>> {
+    terminated(preceded(space0, opt(comment)), line_ending)(s)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub const TOLERANT_INPUT: &str =
+        "\r\n  1000 \t# snack\r\n2000\r\n\r\n\r\n# another elf\n\t3000\n\n\n# end";
+
+    #[test]
+    fn top_elf() {
+        let input = include_str!("./sample.txt");
+        let (res, elves) = parse_elves::<u32>(input).unwrap();
+        assert_eq!(res, "");
+
+        let result = find_elf_with_max_calories(&elves).unwrap();
+        assert_eq!(result.total_calories(), 24000);
+    }
+
+    #[test]
+    fn top_3_elves() {
+        let input = include_str!("./sample.txt");
+        let (res, elves) = parse_elves::<u32>(input).unwrap();
+        assert_eq!(res, "");
+
+        let result = find_top_3_max_calories(&elves).unwrap();
+        assert_eq!(result, 45000);
+    }
+
+    #[test]
+    fn top_k_elves() {
+        let input = include_str!("./sample.txt");
+        let (_, elves) = parse_elves::<u32>(input).unwrap();
+
+        let top: Vec<(usize, u32)> = top_k(&elves, 2)
+            .unwrap()
+            .into_iter()
+            .map(|(i, e)| (i, e.total_calories()))
+            .collect();
+        assert_eq!(top, vec![(3, 24000), (2, 11000)]);
+
+        assert_eq!(top_k(&elves, 5).unwrap().len(), 5);
+        assert!(top_k(&elves, 6).is_none());
+        assert_eq!(
+            top_k_totals([5, 7, 5, 1], 3),
+            Some(vec![(1, 7), (0, 5), (2, 5)])
+        );
+    }
+
+    #[test]
+    fn tolerates_formatting() {
+        let elves: Vec<Elf> = parse_input(TOLERANT_INPUT, Mode::Tolerant).unwrap();
+        let totals: Vec<u32> = elves.iter().map(Elf::total_calories).collect();
+        assert_eq!(totals, vec![3000, 3000]);
+        assert_eq!(elves[0].calories(), &[1000, 2000]);
+
+        assert!(parse_input::<u32>(TOLERANT_INPUT, Mode::Strict).is_err());
+        assert!(parse_input::<u32>("1000\n\n\n2000", Mode::Strict).is_err());
+
+        let sample = include_str!("./sample.txt");
+        let strict: Vec<Elf> = parse_input(sample, Mode::Strict).unwrap();
+        let tolerant: Vec<Elf> = parse_input(sample, Mode::Tolerant).unwrap();
+        assert_eq!(strict.len(), tolerant.len());
+    }
+
+    #[test]
+    fn detects_overflow() {
+        let elves = Day01::parse("4294967295\n\n4294967294\n1\n\n1\n1").unwrap();
+        assert_eq!(elves[1].total_calories(), u32::MAX);
+        assert!(matches!(Day01::part2(&elves), Err(Error::Overflow(_))));
+
+        assert!(matches!(
+            Day01::parse("1\n\n4294967295\n1"),
+            Err(Error::Overflow(_))
+        ));
+        assert!(matches!(
+            Day01::parse("4294967296"),
+            Err(Error::Overflow(_))
+        ));
+
+        let (_, elves) = parse_elves::<u64>("4294967295\n1\n\n4294967296").unwrap();
+        assert_eq!(elves[0].total_calories(), 1 << 32);
+        assert_eq!(
+            find_top_3_max_calories(&elves),
+            Err(Error::NoSolution(
+                "fewer than 3 elves in the input".to_string()
+            ))
+        );
+    }
+}
\ No newline at end of file
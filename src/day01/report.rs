@@ -0,0 +1,275 @@
+//! Summary statistics of the calories carried by the elves.
+
+use std::fmt;
+
+use serde_json::{json, Value};
+
+use super::{Calories, Elf};
+
+pub const DEFAULT_BUCKETS: usize = 10;
+/// The percentiles listed in the report.
+pub const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+/// Statistics over the total calories of every elf and the number of items they carry.
+///
+/// Calories are widened to `u128`, whatever type the elves count them in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalorieReport {
+    pub elves: usize,
+    pub items: usize,
+    pub total: u128,
+    pub min: u128,
+    pub max: u128,
+    pub mean: f64,
+    pub median: f64,
+    /// The population standard deviation.
+    pub std_dev: f64,
+    /// `(percentile, value)` for each of [`PERCENTILES`], interpolated between elves.
+    pub percentiles: Vec<(u8, f64)>,
+    pub items_per_elf: ItemCounts,
+    pub histogram: Vec<Bucket>,
+    /// The elves (index, total) beyond 1.5 interquartile ranges from the quartiles.
+    pub outliers: Vec<(usize, u128)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemCounts {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+}
+
+/// The number of elves whose total is in `start..=last`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bucket {
+    pub start: u128,
+    pub last: u128,
+    pub elves: usize,
+}
+
+impl CalorieReport {
+    /// Builds the report with at most `buckets` histogram buckets, or `None` without elves
+    /// or if the grand total does not fit in `u128`.
+    pub fn new<C: Calories>(elves: &[Elf<C>], buckets: usize) -> Option<Self> {
+        let totals: Vec<u128> = elves.iter().map(|e| e.total_calories().into()).collect();
+        let mut sorted = totals.clone();
+        sorted.sort_unstable();
+
+        let (min, max) = (*sorted.first()?, *sorted.last()?);
+        let n = sorted.len() as f64;
+        let total = u128::checked_sum(sorted.iter().copied())?;
+        let mean = total as f64 / n;
+        let variance = sorted
+            .iter()
+            .map(|&t| (t as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+
+        let (q1, q3) = (percentile(&sorted, 25.0), percentile(&sorted, 75.0));
+        let fence = 1.5 * (q3 - q1);
+        let outliers = totals
+            .iter()
+            .enumerate()
+            .filter(|(_, &t)| (t as f64) < q1 - fence || (t as f64) > q3 + fence)
+            .map(|(i, &t)| (i, t))
+            .collect();
+
+        let items: Vec<usize> = elves.iter().map(|e| e.calories().len()).collect();
+
+        Some(CalorieReport {
+            elves: elves.len(),
+            items: items.iter().sum(),
+            total,
+            min,
+            max,
+            mean,
+            median: percentile(&sorted, 50.0),
+            std_dev: variance.sqrt(),
+            percentiles: PERCENTILES
+                .iter()
+                .map(|&p| (p, percentile(&sorted, p as f64)))
+                .collect(),
+            items_per_elf: ItemCounts {
+                min: *items.iter().min()?,
+                max: *items.iter().max()?,
+                mean: items.iter().sum::<usize>() as f64 / n,
+            },
+            histogram: histogram(&sorted, buckets.max(1)),
+            outliers,
+        })
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "elves": self.elves,
+            "items": self.items,
+            "total": self.total,
+            "min": self.min,
+            "max": self.max,
+            "mean": self.mean,
+            "median": self.median,
+            "std_dev": self.std_dev,
+            "percentiles": self.percentiles
+                .iter()
+                .map(|(p, v)| (format!("p{}", p), json!(v)))
+                .collect::<serde_json::Map<_, _>>(),
+            "items_per_elf": {
+                "min": self.items_per_elf.min,
+                "max": self.items_per_elf.max,
+                "mean": self.items_per_elf.mean,
+            },
+            "histogram": self.histogram
+                .iter()
+                .map(|b| json!({"start": b.start, "last": b.last, "elves": b.elves}))
+                .collect::<Vec<_>>(),
+            "outliers": self.outliers
+                .iter()
+                .map(|(i, t)| json!({"elf": i + 1, "calories": t}))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+// Linear interpolation between the closest ranks of `sorted`, which must not be empty.
+fn percentile(sorted: &[u128], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - low as f64;
+    sorted[low] as f64 * (1.0 - weight) + sorted[high] as f64 * weight
+}
+
+// At most `buckets` equal-width buckets from the smallest to the largest total. The width only
+// falls short of covering every total when it does not fit in `u128`, in which case the last
+// bucket is stretched to the largest total.
+fn histogram(sorted: &[u128], buckets: usize) -> Vec<Bucket> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let width = ((max - min) / buckets as u128).saturating_add(1);
+    let count = ((max - min) / width).min(buckets as u128 - 1) as usize + 1;
+
+    let mut histogram: Vec<Bucket> = (0..count)
+        .map(|i| {
+            let start = min + i as u128 * width;
+            let last = start.saturating_add(width - 1);
+            Bucket {
+                start,
+                last: if i + 1 == count { last.max(max) } else { last },
+                elves: 0,
+            }
+        })
+        .collect();
+    for &total in sorted {
+        histogram[((total - min) / width).min(count as u128 - 1) as usize].elves += 1;
+    }
+
+    histogram
+}
+
+impl fmt::Display for CalorieReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16} {:>12}", "Elves", self.elves)?;
+        writeln!(f, "{:<16} {:>12}", "Items", self.items)?;
+        writeln!(f, "{:<16} {:>12}", "Total", self.total)?;
+        writeln!(f, "{:<16} {:>12}", "Min", self.min)?;
+        writeln!(f, "{:<16} {:>12}", "Max", self.max)?;
+        writeln!(f, "{:<16} {:>12.2}", "Mean", self.mean)?;
+        writeln!(f, "{:<16} {:>12.2}", "Median", self.median)?;
+        writeln!(f, "{:<16} {:>12.2}", "Std dev", self.std_dev)?;
+        for (p, value) in &self.percentiles {
+            writeln!(f, "{:<16} {:>12.2}", format!("P{}", p), value)?;
+        }
+        writeln!(
+            f,
+            "{:<16} {:>12}",
+            "Items per elf",
+            format!(
+                "{}-{}, {:.2}",
+                self.items_per_elf.min, self.items_per_elf.max, self.items_per_elf.mean
+            )
+        )?;
+
+        writeln!(f, "Histogram:")?;
+        let widest = self.histogram.iter().map(|b| b.elves).max().unwrap_or(0);
+        for bucket in &self.histogram {
+            let bar = "#".repeat((bucket.elves * 40).div_ceil(widest.max(1)));
+            writeln!(
+                f,
+                "  {:>10} - {:<10} {:>5} {}",
+                bucket.start, bucket.last, bucket.elves, bar
+            )?;
+        }
+
+        write!(f, "Outliers:")?;
+        if self.outliers.is_empty() {
+            write!(f, " none")?;
+        }
+        for (i, total) in &self.outliers {
+            write!(f, " elf {} ({})", i + 1, total)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_elves;
+    use super::*;
+
+    #[test]
+    fn sample_report() {
+        let (_, elves) = parse_elves::<u32>(include_str!("./sample.txt")).unwrap();
+        let report = CalorieReport::new(&elves, 4).unwrap();
+
+        assert_eq!(report.elves, 5);
+        assert_eq!(report.items, 10);
+        assert_eq!(report.total, 55000);
+        assert_eq!(report.mean, 11000.0);
+        assert_eq!(report.median, 10000.0);
+        assert_eq!(report.std_dev.round(), 6986.0);
+        assert_eq!(report.percentiles[1], (25, 6000.0));
+        assert_eq!(report.items_per_elf.mean, 2.0);
+        assert_eq!(
+            report
+                .histogram
+                .iter()
+                .map(|b| (b.start, b.elves))
+                .collect::<Vec<_>>(),
+            vec![(4000, 2), (9001, 2), (14002, 0), (19003, 1)]
+        );
+        assert_eq!(report.outliers, vec![(3, 24000)]);
+    }
+
+    #[test]
+    fn single_elf_report() {
+        let (_, elves) = parse_elves::<u64>("100\n200").unwrap();
+        let report = CalorieReport::new(&elves, DEFAULT_BUCKETS).unwrap();
+
+        assert_eq!(report.median, 300.0);
+        assert_eq!(report.std_dev, 0.0);
+        assert_eq!(report.histogram.len(), 1);
+        assert!(report.outliers.is_empty());
+
+        assert!(CalorieReport::new::<u32>(&[], DEFAULT_BUCKETS).is_none());
+    }
+
+    #[test]
+    fn histogram_near_the_limit() {
+        let bounds = |sorted: &[u128], buckets| {
+            let histogram = histogram(sorted, buckets);
+            let bounds = histogram.iter().map(|b| (b.start, b.last, b.elves));
+            bounds.collect::<Vec<_>>()
+        };
+        const MAX: u128 = u128::MAX;
+
+        assert_eq!(
+            bounds(&[MAX - 10, MAX - 1, MAX], 2),
+            vec![(MAX - 10, MAX - 5, 1), (MAX - 4, MAX, 2)]
+        );
+        assert_eq!(bounds(&[0, MAX], 1), vec![(0, MAX, 2)]);
+
+        let (_, elves) = parse_elves::<u128>(&format!("0\n\n{}", MAX)).unwrap();
+        let report = CalorieReport::new(&elves, 4).unwrap();
+        assert_eq!(report.histogram.len(), 4);
+        assert_eq!(report.histogram.last().unwrap().last, MAX);
+        assert_eq!(report.histogram.iter().map(|b| b.elves).sum::<usize>(), 2);
+    }
+}
@@ -0,0 +1,233 @@
+//! Reading elf totals one elf at a time, for inventories too large to hold in memory.
+
+use std::io::BufRead;
+use std::marker::PhantomData;
+
+use crate::error::parse_all;
+use crate::{Error, Result};
+
+use super::{top_k_totals, Calories, Mode};
+
+/// The total calories of every elf read from `reader`, in input order, counted in `C`.
+///
+/// Only the current line is kept in memory. The input follows the same format as
+/// [`parse_input`](super::parse_input) in the same `mode`, and errors report the position
+/// in the whole input.
+pub struct ElfTotals<R, C = u32> {
+    reader: R,
+    mode: Mode,
+    buffer: String,
+    line: usize,
+    after_blank_line: bool,
+    done: bool,
+    calories: PhantomData<C>,
+}
+
+impl<R: BufRead, C: Calories> ElfTotals<R, C> {
+    pub fn new(reader: R, mode: Mode) -> Self {
+        ElfTotals {
+            reader,
+            mode,
+            buffer: String::new(),
+            line: 0,
+            after_blank_line: false,
+            done: false,
+            calories: PhantomData,
+        }
+    }
+
+    // An error at the current line, or at the first one of an empty input.
+    fn error_here(&self) -> Error {
+        Error::Parse {
+            line: self.line.max(1),
+            column: 1,
+            snippet: String::new(),
+        }
+    }
+
+    fn read_total(&mut self) -> Result<Option<C>> {
+        let mut total = None;
+        loop {
+            self.buffer.clear();
+            let read = self
+                .reader
+                .read_line(&mut self.buffer)
+                .map_err(|e| Error::Io(e.to_string()))?;
+
+            let tolerant = self.mode == Mode::Tolerant;
+            if read == 0 {
+                // In strict mode, a blank line must be followed by another elf, and the input
+                // must have at least one.
+                let missing_elf = self.after_blank_line || self.line == 0;
+                return match total {
+                    None if missing_elf && !tolerant => Err(self.error_here()),
+                    _ => Ok(total),
+                };
+            }
+            self.line += 1;
+
+            let mut line = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+            let mut padding = 0;
+            if tolerant {
+                line = line.strip_suffix('\r').unwrap_or(line);
+                let comment = line.find('#');
+                if let Some(i) = comment {
+                    line = &line[..i];
+                }
+
+                let trimmed = line.trim_start_matches([' ', '\t']);
+                padding = line.len() - trimmed.len();
+                line = trimmed.trim_end_matches([' ', '\t']);
+
+                if line.is_empty() && comment.is_some() {
+                    continue;
+                }
+            }
+
+            if line.is_empty() {
+                self.after_blank_line = true;
+                match total {
+                    Some(total) => return Ok(Some(total)),
+                    None if tolerant => continue,
+                    None => return Err(self.error_here()),
+                }
+            }
+
+            let calories = parse_all(line, C::parse).map_err(|e| match e {
+                Error::Parse {
+                    column, snippet, ..
+                } => Error::Parse {
+                    line: self.line,
+                    column: column + padding,
+                    snippet,
+                },
+                Error::TrailingInput {
+                    column, snippet, ..
+                } => Error::TrailingInput {
+                    line: self.line,
+                    column: column + padding,
+                    snippet,
+                },
+                Error::Overflow(_) => Error::Overflow(format!("the value at line {}", self.line)),
+                e => e,
+            })?;
+            self.after_blank_line = false;
+            total = Some(
+                calories
+                    .checked_add(total.unwrap_or(C::ZERO))
+                    .ok_or_else(|| {
+                        Error::Overflow(format!("the total of the elf at line {}", self.line))
+                    })?,
+            );
+        }
+    }
+}
+
+impl<R: BufRead, C: Calories> Iterator for ElfTotals<R, C> {
+    type Item = Result<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let total = self.read_total();
+        if !matches!(total, Ok(Some(_))) {
+            self.done = true;
+        }
+        total.transpose()
+    }
+}
+
+/// Streams the elves from `reader` and returns the `k` largest totals with their index,
+/// largest first, or `None` if there are fewer than `k` elves. See [`top_k_totals`].
+pub fn top_k_from_reader<C: Calories>(
+    reader: impl BufRead,
+    mode: Mode,
+    k: usize,
+) -> Result<Option<Vec<(usize, C)>>> {
+    let mut error = None;
+    let totals = ElfTotals::<_, C>::new(reader, mode).map_while(|total| match total {
+        Ok(total) => Some(total),
+        Err(e) => {
+            error = Some(e);
+            None
+        }
+    });
+
+    let top = top_k_totals(totals, k);
+    match error {
+        Some(e) => Err(e),
+        None => Ok(top),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_input;
+    use super::super::tests::TOLERANT_INPUT;
+    use super::*;
+
+    #[test]
+    fn streams_sample_totals() {
+        let input = include_str!("./sample.txt");
+        let totals: Result<Vec<u32>> = ElfTotals::new(input.as_bytes(), Mode::Strict).collect();
+        assert_eq!(totals, Ok(vec![6000, 4000, 11000, 24000, 10000]));
+
+        let top = top_k_from_reader::<u32>(input.as_bytes(), Mode::Strict, 3)
+            .unwrap()
+            .unwrap();
+        assert_eq!(top, vec![(3, 24000), (2, 11000), (4, 10000)]);
+    }
+
+    #[test]
+    fn reports_errors_with_their_line() {
+        let totals: Vec<Result<u32>> =
+            ElfTotals::new("100\n\n200\n\n\n300\n".as_bytes(), Mode::Strict).collect();
+        assert_eq!(
+            totals,
+            vec![
+                Ok(100),
+                Ok(200),
+                Err(Error::Parse {
+                    line: 5,
+                    column: 1,
+                    snippet: String::new()
+                })
+            ]
+        );
+
+        let empty = top_k_from_reader::<u32>("".as_bytes(), Mode::Strict, 1);
+        assert_eq!(
+            empty.unwrap_err(),
+            parse_input::<u32>("", Mode::Strict).unwrap_err()
+        );
+
+        let error = top_k_from_reader::<u32>("100\n20x\n".as_bytes(), Mode::Strict, 1).unwrap_err();
+        assert_eq!(
+            error,
+            Error::TrailingInput {
+                line: 2,
+                column: 3,
+                snippet: "x".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn streams_tolerant_input() {
+        let totals: Result<Vec<u32>> =
+            ElfTotals::new(TOLERANT_INPUT.as_bytes(), Mode::Tolerant).collect();
+        assert_eq!(totals, Ok(vec![3000, 3000]));
+
+        let error = top_k_from_reader::<u32>("1\n\n  2x\n".as_bytes(), Mode::Tolerant, 1);
+        assert_eq!(
+            error,
+            Err(Error::TrailingInput {
+                line: 3,
+                column: 4,
+                snippet: "x".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! A round by round account of how a strategy guide is scored.
+
+use std::fmt;
+
+use serde_json::{json, Value};
+
+use super::{GameOutcome, PlayChoice, RuleTable};
+
+/// How one round was played and scored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundTrace {
+    /// The number of the round, from 1.
+    pub round: usize,
+    pub opponent: PlayChoice,
+    pub response: PlayChoice,
+    /// The outcome the guide asked for, which decided the response (part 2 only).
+    pub wanted: Option<GameOutcome>,
+    pub outcome: GameOutcome,
+    pub shape_score: i32,
+    pub outcome_score: i32,
+    /// The total score up to and including this round.
+    pub running_total: i32,
+}
+
+/// Every round of a guide, with how many were won, tied and lost.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub rounds: Vec<RoundTrace>,
+    pub wins: usize,
+    pub ties: usize,
+    pub losses: usize,
+}
+
+impl Explanation {
+    /// Explains `(opponent, response)` rounds, as in part 1.
+    pub fn new(games: &[(PlayChoice, PlayChoice)]) -> Self {
+        Explanation::from_rounds(games.iter().map(|&(a, b)| (a, b, None)))
+    }
+
+    /// Explains `(opponent, outcome)` rounds, playing the response reaching each outcome as in part 2.
+    pub fn from_outcomes(rounds: &[(PlayChoice, GameOutcome)]) -> Self {
+        Explanation::from_rounds(
+            rounds
+                .iter()
+                .map(|&(a, outcome)| (a, a.response_for_outcome(outcome), Some(outcome))),
+        )
+    }
+
+    fn from_rounds(
+        rounds: impl Iterator<Item = (PlayChoice, PlayChoice, Option<GameOutcome>)>,
+    ) -> Self {
+        let rules = RuleTable::classic();
+        let mut explanation = Explanation {
+            rounds: vec![],
+            wins: 0,
+            ties: 0,
+            losses: 0,
+        };
+
+        let mut running_total = 0;
+        for (i, (opponent, response, wanted)) in rounds.enumerate() {
+            let outcome = rules.outcome(response.to_move(), opponent.to_move());
+            match outcome {
+                GameOutcome::Win => explanation.wins += 1,
+                GameOutcome::Tie => explanation.ties += 1,
+                GameOutcome::Lose => explanation.losses += 1,
+            }
+
+            let shape_score = rules.move_score(response.to_move());
+            let outcome_score = rules.outcome_score(outcome);
+            running_total += shape_score + outcome_score;
+
+            explanation.rounds.push(RoundTrace {
+                round: i + 1,
+                opponent,
+                response,
+                wanted,
+                outcome,
+                shape_score,
+                outcome_score,
+                running_total,
+            });
+        }
+
+        explanation
+    }
+
+    /// The total score of the guide.
+    pub fn total(&self) -> i32 {
+        self.rounds.last().map_or(0, |r| r.running_total)
+    }
+
+    /// One JSON object per round, then one with the counts.
+    pub fn json_lines(&self) -> Vec<Value> {
+        let rules = RuleTable::classic();
+        let mut lines: Vec<Value> = self
+            .rounds
+            .iter()
+            .map(|r| {
+                json!({
+                    "round": r.round,
+                    "opponent": rules.name(r.opponent.to_move()),
+                    "response": rules.name(r.response.to_move()),
+                    "wanted": r.wanted.map(outcome_name),
+                    "outcome": outcome_name(r.outcome),
+                    "shape_score": r.shape_score,
+                    "outcome_score": r.outcome_score,
+                    "total": r.running_total,
+                })
+            })
+            .collect();
+
+        lines.push(json!({
+            "wins": self.wins,
+            "ties": self.ties,
+            "losses": self.losses,
+            "total": self.total(),
+        }));
+        lines
+    }
+}
+
+fn outcome_name(outcome: GameOutcome) -> &'static str {
+    match outcome {
+        GameOutcome::Lose => "lose",
+        GameOutcome::Tie => "tie",
+        GameOutcome::Win => "win",
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules = RuleTable::classic();
+
+        writeln!(
+            f,
+            "{:>6}  {:<9} {:<19} {:<8} {:>5} {:>7} {:>7}",
+            "Round", "Opponent", "Response", "Outcome", "Shape", "Outcome", "Total"
+        )?;
+        for r in &self.rounds {
+            let response = match r.wanted {
+                Some(wanted) => format!(
+                    "{} (to {})",
+                    rules.name(r.response.to_move()),
+                    outcome_name(wanted)
+                ),
+                None => rules.name(r.response.to_move()).to_string(),
+            };
+            writeln!(
+                f,
+                "{:>6}  {:<9} {:<19} {:<8} {:>5} {:>7} {:>7}",
+                r.round,
+                rules.name(r.opponent.to_move()),
+                response,
+                outcome_name(r.outcome),
+                r.shape_score,
+                r.outcome_score,
+                r.running_total
+            )?;
+        }
+
+        write!(
+            f,
+            "{} wins, {} ties, {} losses, total score {}",
+            self.wins,
+            self.ties,
+            self.losses,
+            self.total()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_games, parse_outcomes};
+    use super::*;
+
+    #[test]
+    fn explains_sample_rounds() {
+        let (_, games) = parse_games(include_str!("./sample.txt")).unwrap();
+        let explanation = Explanation::new(&games);
+
+        assert_eq!(
+            (explanation.wins, explanation.ties, explanation.losses),
+            (1, 1, 1)
+        );
+        assert_eq!(explanation.total(), 15);
+        assert_eq!(
+            explanation.rounds[0],
+            RoundTrace {
+                round: 1,
+                opponent: PlayChoice::Rock,
+                response: PlayChoice::Paper,
+                wanted: None,
+                outcome: GameOutcome::Win,
+                shape_score: 2,
+                outcome_score: 6,
+                running_total: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn explains_forced_responses() {
+        let (_, rounds) = parse_outcomes(include_str!("./sample.txt")).unwrap();
+        let explanation = Explanation::from_outcomes(&rounds);
+
+        assert_eq!(explanation.total(), 12);
+        let second = &explanation.rounds[1];
+        assert_eq!(second.wanted, Some(GameOutcome::Lose));
+        assert_eq!(second.response, PlayChoice::Rock);
+        assert_eq!(second.running_total, 5);
+
+        let lines = explanation.json_lines();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[3],
+            json!({"wins": 1, "ties": 1, "losses": 1, "total": 12})
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Reading the second column of a strategy guide both ways at once.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use nom::bytes::complete::take_till1;
+use nom::character::complete::{newline, space1};
+use nom::combinator::map;
+use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+use nom::IResult;
+use serde_json::{json, Value};
+
+use super::{GameOutcome, Guide, PlayChoice, SymbolMapping};
+use crate::error::parse_error_at;
+use crate::Result;
+
+/// A round of the guide, whose second column is kept as written until it is read as a
+/// response or as an outcome.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuideRound<'a> {
+    pub opponent: PlayChoice,
+    /// The token of the second column, a slice of the parsed input.
+    pub second: &'a str,
+}
+
+impl GuideRound<'_> {
+    /// The round as `(opponent, response)` when the second column is a move (part 1), or
+    /// `None` if its token is not a response of `mapping`.
+    pub fn as_move(&self, mapping: &SymbolMapping) -> Option<(PlayChoice, PlayChoice)> {
+        let (_, response) = mapping.response(self.second).ok()?;
+        Some((self.opponent, response))
+    }
+
+    /// The round as `(opponent, response)` when the second column is an outcome (part 2), or
+    /// `None` if its token is not an outcome of `mapping`.
+    pub fn as_outcome(&self, mapping: &SymbolMapping) -> Option<(PlayChoice, PlayChoice)> {
+        let outcome = self.outcome(mapping)?;
+        Some((self.opponent, self.opponent.response_for_outcome(outcome)))
+    }
+
+    /// The outcome the second column asks for, or `None` if its token is not an outcome of
+    /// `mapping`.
+    pub fn outcome(&self, mapping: &SymbolMapping) -> Option<GameOutcome> {
+        let (_, outcome) = mapping.outcome(self.second).ok()?;
+        Some(outcome)
+    }
+}
+
+/// Parses every round once, reading the opponent with `mapping` and keeping the second
+/// column as a token.
+pub fn parse_guide_with<'a>(
+    input: &'a str,
+    mapping: &SymbolMapping,
+) -> IResult<&'a str, Vec<GuideRound<'a>>> {
+    let round = separated_pair(
+        |s| mapping.opponent(s),
+        space1,
+        take_till1(char::is_whitespace),
+    );
+    let round = map(round, |(opponent, second)| GuideRound { opponent, second });
+    separated_list1(newline, round)(input)
+}
+
+/// Reads every round with `read`, failing at the first token it cannot read.
+pub(super) fn read_rounds<T>(
+    input: &str,
+    rounds: &[GuideRound],
+    read: impl Fn(&GuideRound) -> Option<T>,
+) -> Result<Vec<T>> {
+    rounds
+        .iter()
+        .map(|round| read(round).ok_or_else(|| parse_error_at(input, round.second)))
+        .collect()
+}
+
+/// What the second column of the guide stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    Moves,
+    Outcomes,
+}
+
+impl fmt::Display for Interpretation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Interpretation::Moves => write!(f, "moves"),
+            Interpretation::Outcomes => write!(f, "outcomes"),
+        }
+    }
+}
+
+/// The total score and outcome counts of the guide under one interpretation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    pub interpretation: Interpretation,
+    pub total: i32,
+    pub wins: usize,
+    pub ties: usize,
+    pub losses: usize,
+}
+
+impl Reading {
+    fn new(
+        interpretation: Interpretation,
+        games: impl Iterator<Item = (PlayChoice, PlayChoice)>,
+    ) -> Self {
+        let mut reading = Reading {
+            interpretation,
+            total: 0,
+            wins: 0,
+            ties: 0,
+            losses: 0,
+        };
+        for (a, b) in games {
+            reading.total += b.game_score(&a);
+            match b.game(&a) {
+                GameOutcome::Win => reading.wins += 1,
+                GameOutcome::Tie => reading.ties += 1,
+                GameOutcome::Lose => reading.losses += 1,
+            }
+        }
+        reading
+    }
+
+    /// How many more rounds are won than lost.
+    pub fn margin(&self) -> i64 {
+        self.wins as i64 - self.losses as i64
+    }
+}
+
+/// Both interpretations of a guide, side by side; a reading is `None` if the guide cannot be
+/// read that way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub moves: Option<Reading>,
+    pub outcomes: Option<Reading>,
+}
+
+impl Comparison {
+    pub fn new(guide: &Guide) -> Self {
+        let reading = |interpretation, games: Result<&[(PlayChoice, PlayChoice)]>| {
+            let games = games.ok()?;
+            Some(Reading::new(interpretation, games.iter().copied()))
+        };
+        Comparison {
+            moves: reading(Interpretation::Moves, guide.games()),
+            outcomes: reading(Interpretation::Outcomes, guide.games_v2()),
+        }
+    }
+
+    /// The more plausible interpretation, or `None` if they are as plausible.
+    ///
+    /// A guide is meant to help win, so the interpretation winning more rounds than it loses
+    /// by the widest margin is the more plausible one. A reading that is not available is
+    /// never guessed.
+    pub fn guess(&self) -> Option<Interpretation> {
+        match (&self.moves, &self.outcomes) {
+            (Some(moves), Some(outcomes)) => match moves.margin().cmp(&outcomes.margin()) {
+                Ordering::Greater => Some(Interpretation::Moves),
+                Ordering::Less => Some(Interpretation::Outcomes),
+                Ordering::Equal => None,
+            },
+            (Some(moves), None) => Some(moves.interpretation),
+            (None, Some(outcomes)) => Some(outcomes.interpretation),
+            (None, None) => None,
+        }
+    }
+
+    fn readings(&self) -> [(Interpretation, Option<&Reading>); 2] {
+        [
+            (Interpretation::Moves, self.moves.as_ref()),
+            (Interpretation::Outcomes, self.outcomes.as_ref()),
+        ]
+    }
+
+    /// One JSON object per interpretation, then one with the guess.
+    pub fn json_lines(&self) -> Vec<Value> {
+        let mut lines: Vec<Value> = self
+            .readings()
+            .iter()
+            .map(|(interpretation, reading)| match reading {
+                Some(r) => json!({
+                    "interpretation": interpretation.to_string(),
+                    "available": true,
+                    "total": r.total,
+                    "wins": r.wins,
+                    "ties": r.ties,
+                    "losses": r.losses,
+                }),
+                None => json!({
+                    "interpretation": interpretation.to_string(),
+                    "available": false,
+                }),
+            })
+            .collect();
+        lines.push(json!({"guess": self.guess().map(|i| i.to_string())}));
+        lines
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<12} {:>9} {:>6} {:>6} {:>6}",
+            "Reading as", "Total", "Wins", "Ties", "Losses"
+        )?;
+        for (interpretation, reading) in self.readings() {
+            match reading {
+                Some(r) => writeln!(
+                    f,
+                    "{:<12} {:>9} {:>6} {:>6} {:>6}",
+                    interpretation.to_string(),
+                    r.total,
+                    r.wins,
+                    r.ties,
+                    r.losses
+                )?,
+                None => writeln!(f, "{:<12} unavailable", interpretation.to_string())?,
+            }
+        }
+
+        match self.guess() {
+            Some(guess) => write!(f, "The second column most likely lists {}", guess),
+            None => write!(f, "Both readings are as plausible"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_with;
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn compares_sample_readings() {
+        let input = include_str!("./sample.txt");
+        let (rest, rounds) = parse_guide_with(input, SymbolMapping::classic()).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(rounds[0].second, "Y");
+
+        let guide = parse_with(input, SymbolMapping::classic()).unwrap();
+        assert_eq!(
+            guide.outcomes().unwrap()[0],
+            (PlayChoice::Rock, GameOutcome::Tie)
+        );
+
+        let comparison = Comparison::new(&guide);
+        assert_eq!(comparison.moves.as_ref().unwrap().total, 15);
+        assert_eq!(comparison.outcomes.as_ref().unwrap().total, 12);
+        // One win and one loss either way.
+        assert_eq!(comparison.guess(), None);
+    }
+
+    #[test]
+    fn guesses_from_the_win_margin() {
+        let classic = SymbolMapping::classic();
+        let comparison = Comparison::new(&parse_with("A Y\nB Z\nC Z", classic).unwrap());
+
+        // As moves: win, win, tie. As outcomes: tie, win, win.
+        let (moves, outcomes) = (comparison.moves.unwrap(), comparison.outcomes.unwrap());
+        assert_eq!((moves.wins, moves.ties), (2, 1));
+        assert_eq!((outcomes.wins, outcomes.ties), (2, 1));
+
+        let comparison = Comparison::new(&parse_with("A Y\nB X\nC X", classic).unwrap());
+        assert_eq!(comparison.guess(), Some(Interpretation::Moves));
+        assert_eq!(comparison.json_lines()[2], json!({"guess": "moves"}));
+    }
+
+    #[test]
+    fn marks_unreadable_interpretations() {
+        let mapping =
+            SymbolMapping::parse("[response]\nrock = \"rock\"\npaper = \"paper\"").unwrap();
+        let guide = parse_with("A paper\nB X\nC rock", &mapping);
+        assert_eq!(
+            guide,
+            Err(Error::Parse {
+                line: 2,
+                column: 3,
+                snippet: "X".to_string()
+            })
+        );
+
+        let guide = parse_with("A paper\nC rock", &mapping).unwrap();
+        assert!(guide.outcomes().is_err());
+
+        let comparison = Comparison::new(&guide);
+        assert_eq!(comparison.moves.as_ref().unwrap().total, 15);
+        assert_eq!(comparison.outcomes, None);
+        assert_eq!(comparison.guess(), Some(Interpretation::Moves));
+        assert_eq!(
+            comparison.json_lines()[1],
+            json!({"interpretation": "outcomes", "available": false})
+        );
+    }
+}
@@ -1,172 +1,239 @@
-use nom::bytes::streaming::tag;
-use nom::character::complete::{anychar, newline};
-use nom::IResult;
-use nom::multi::separated_list1;
-use nom::sequence::separated_pair;
-
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022_rust::cli::{self, Args};
+use advent_of_code_2022_rust::day02::{
+    self, Comparison, Constraints, Day02, Explanation, Fixed, FrequencyCounter, Guide, GuideReplay,
+    PlayChoice, Player, Random, SymbolMapping, WinStayLoseShift,
+};
+use advent_of_code_2022_rust::{solution, Report, Solution};
+use serde_json::json;
+
+const LABELS: [&str; 2] = ["Total score", "Total score V2"];
+const DEFAULT_SEED: u64 = 2022;
+
+/// Usage: `day02 [--mapping <file>] [--explain] [--compare] [--optimal [--max-wins N]
+/// [--max-repeat K]] [--tournament [--rounds N] [--seed S]]`, plus the options of every day binary (see [`cli`]).
+///
+/// `--mapping` reads the symbols of the strategy guide from a TOML file, see [`SymbolMapping`].
+/// `--explain` also prints how every round is scored, see [`Explanation`].
+/// `--compare` reads the second column as moves and as outcomes side by side, and guesses which
+/// one it is, see [`Comparison`].
+/// `--optimal` prints the best score against the opponent's moves and how far each guide is
+/// from it, see [`day02::optimal_plan`].
+/// `--tournament` plays every strategy against each other and the guides, `N` rounds per match
+/// (the length of the guide by default), see [`day02::round_robin`].
 fn main() {
-    let input = include_str!("./data.txt");
-    let (res, games) = parse_games(input).unwrap();
-    assert_eq!(res, "");
-
-    let total_score = games.iter().map(|(a, b)| b.game_score(a)).sum::<i32>();
-    println!("Total score: {}", total_score);
-
-    let (res, games) = parse_games_v2(input).unwrap();
-    assert_eq!(res, "");
+    let mut args = Args::from_env();
+    let explain = args.flag("--explain");
+    let compare = args.flag("--compare");
+    let optimal = args.flag("--optimal");
+    let tournament = args.flag("--tournament");
+    let result = (|| {
+        let rounds = number(args.value("--rounds")?, "--rounds", 1)?;
+        let seed = match args.value("--seed")? {
+            Some(seed) => Some(
+                seed.parse()
+                    .map_err(|_| format!("Invalid --seed: {}", seed))?,
+            ),
+            None => None,
+        };
+        if !tournament && (rounds.is_some() || seed.is_some()) {
+            return Err("--rounds and --seed require --tournament".to_string());
+        }
 
-    let total_score = games.iter().map(|(a, b)| b.game_score(a)).sum::<i32>();
-    println!("Total score V2: {}", total_score);
-}
+        let constraints = Constraints {
+            max_wins: number(args.value("--max-wins")?, "--max-wins", 0)?,
+            max_repeat: number(args.value("--max-repeat")?, "--max-repeat", 1)?,
+        };
+        if !optimal && constraints != Constraints::default() {
+            return Err("--max-wins and --max-repeat require --optimal".to_string());
+        }
 
-#[derive(Copy, Debug, Clone, PartialEq)]
-enum PlayChoice {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
-}
+        let mapping = match args.value("--mapping")? {
+            Some(path) => SymbolMapping::load(Path::new(&path))?,
+            None => SymbolMapping::classic().clone(),
+        };
+        let options = args.options()?;
+
+        let input = options.read_input(Day02::DAY)?;
+        let start = Instant::now();
+        let guide = day02::parse_with(&input, &mapping).map_err(|e| e.to_string())?;
+        let (report, unavailable) = solve(&guide, start.elapsed(), options.part);
+        cli::print_report(&report, &options, LABELS);
+        for (part, e) in unavailable {
+            if options.json {
+                let line = json!({"day": Day02::DAY, "part": part, "available": false, "error": e});
+                println!("{}", line);
+            } else {
+                println!("{}: unavailable, {}", LABELS[part as usize - 1], e);
+            }
+        }
 
-#[derive(Copy, Debug, Clone, PartialEq)]
-enum GameOutcome {
-    Lose = 0,
-    Tie = 3,
-    Win = 6,
-}
+        if explain {
+            for part in &report.parts {
+                // Only the solved parts are explained, so their reading is available.
+                let explanation = if part.part == 1 {
+                    Explanation::new(guide.games().map_err(|e| e.to_string())?)
+                } else {
+                    Explanation::from_outcomes(guide.outcomes().map_err(|e| e.to_string())?)
+                };
+                print_explanation(part.part, &explanation, options.json);
+            }
+        }
 
-impl GameOutcome {
-    fn score(&self) -> i32 {
-        *self as i32
-    }
+        if compare {
+            let comparison = Comparison::new(&guide);
+            if options.json {
+                for line in comparison.json_lines() {
+                    println!("{}", line);
+                }
+            } else {
+                println!("\n{}", comparison);
+            }
+        }
 
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, c) = anychar(input)?;
-        match c {
-            'X' => Ok((input, GameOutcome::Lose)),
-            'Y' => Ok((input, GameOutcome::Tie)),
-            'Z' => Ok((input, GameOutcome::Win)),
-            _ => Err(nom::Err::Error(nom::error::Error::new(
-                input,
-                nom::error::ErrorKind::OneOf,
-            ))),
+        if optimal {
+            let parts = options.part.map_or(vec![1, 2], |part| vec![part]);
+            print_optimal(&guide, &parts, &constraints, options.json);
         }
-    }
-}
 
-impl PlayChoice {
-    fn game_score(&self, other: &Self) -> i32 {
-        self.game(other).score() + self.score()
-    }
+        if tournament {
+            let rounds = rounds.unwrap_or(guide.opponents().len());
+            let leaderboard =
+                day02::round_robin(&mut players(&guide, seed.unwrap_or(DEFAULT_SEED)), rounds);
+            if options.json {
+                for line in leaderboard.json_lines() {
+                    println!("{}", line);
+                }
+            } else {
+                println!("\n{}", leaderboard);
+            }
+        }
+        Ok(())
+    })();
 
-    pub fn score(&self) -> i32 {
-        *self as i32
-    }
+    cli::exit_on_error(result);
+}
 
-    fn game(&self, other: &Self) -> GameOutcome {
-        match (self, other) {
-            (PlayChoice::Rock, PlayChoice::Rock) => GameOutcome::Tie,
-            (PlayChoice::Rock, PlayChoice::Paper) => GameOutcome::Lose,
-            (PlayChoice::Rock, PlayChoice::Scissors) => GameOutcome::Win,
-            (PlayChoice::Paper, PlayChoice::Rock) => GameOutcome::Win,
-            (PlayChoice::Paper, PlayChoice::Paper) => GameOutcome::Tie,
-            (PlayChoice::Paper, PlayChoice::Scissors) => GameOutcome::Lose,
-            (PlayChoice::Scissors, PlayChoice::Rock) => GameOutcome::Lose,
-            (PlayChoice::Scissors, PlayChoice::Paper) => GameOutcome::Win,
-            (PlayChoice::Scissors, PlayChoice::Scissors) => GameOutcome::Tie,
+// Solves the requested parts of `guide`, returning the parts whose reading is not available
+// with their error instead of failing, since `--compare` and `--optimal` still apply to the
+// other one.
+fn solve(guide: &Guide, parse_time: Duration, part: Option<u8>) -> (Report, Vec<(u8, String)>) {
+    let mut report = Report {
+        day: Day02::DAY,
+        parse_time,
+        parts: vec![],
+    };
+    let mut unavailable = vec![];
+    for part in part.map_or(vec![1, 2], |part| vec![part]) {
+        match solution::solve_parsed::<Day02>(guide, parse_time, Some(part)) {
+            Ok(solved) => report.parts.extend(solved.parts),
+            Err(e) => unavailable.push((part, e.to_string())),
         }
     }
+    (report, unavailable)
+}
 
-    fn parse_pair(input: &str) -> IResult<&str, (Self, Self)> {
-        separated_pair(PlayChoice::parse, tag(" "), PlayChoice::parse_second)(input)
-    }
-
-    fn parse_pair_v2(input: &str) -> IResult<&str, (Self, Self)> {
-        let (input, a) = PlayChoice::parse(input)?;
-        let (input, _) = tag(" ")(input)?;
-        let (input, outcome) = GameOutcome::parse(input)?;
-
-        let b = a.response_for_outcome(outcome);
-
-        Ok((input, (a, b)))
-    }
+fn number(value: Option<String>, name: &str, min: usize) -> Result<Option<usize>, String> {
+    value
+        .map(|v| match v.parse() {
+            Ok(n) if n >= min => Ok(n),
+            _ => Err(format!("Invalid {}: {}", name, v)),
+        })
+        .transpose()
+}
 
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, c) = anychar(input)?;
-        match c {
-            'A' => Ok((input, PlayChoice::Rock)),
-            'B' => Ok((input, PlayChoice::Paper)),
-            'C' => Ok((input, PlayChoice::Scissors)),
-            _ => Err(nom::Err::Error(nom::error::Error::new(
-                input,
-                nom::error::ErrorKind::OneOf,
-            ))),
+// Only the parts in `parts` whose reading is available are compared.
+fn print_optimal(guide: &Guide, parts: &[u8], constraints: &Constraints, json: bool) {
+    let opponents = guide.opponents();
+    let plan = day02::optimal_plan(&opponents, constraints)
+        .expect("--max-repeat is at least 1, so a plan always exists");
+    let responses: String = plan.responses.iter().map(letter).collect();
+
+    let gaps = parts.iter().filter_map(|&part| {
+        let games = if part == 1 {
+            guide.games()
+        } else {
+            guide.games_v2()
         }
+        .ok()?;
+        let score = day02::total_score(games);
+        let played: Vec<PlayChoice> = games.iter().map(|(_, response)| *response).collect();
+        Some((part, score, constraints.allows(&opponents, &played)))
+    });
+
+    if json {
+        let guides: Vec<_> = gaps
+            .map(|(part, score, allowed)| {
+                json!({"part": part, "score": score, "gap": plan.score - score, "allowed": allowed})
+            })
+            .collect();
+        let line = json!({"optimal": plan.score, "responses": responses, "guides": guides});
+        println!("{}", line);
+        return;
     }
 
-    fn parse_second(input: &str) -> IResult<&str, Self> {
-        let (input, c) = anychar(input)?;
-        match c {
-            'X' => Ok((input, PlayChoice::Rock)),
-            'Y' => Ok((input, PlayChoice::Paper)),
-            'Z' => Ok((input, PlayChoice::Scissors)),
-            _ => Err(nom::Err::Error(nom::error::Error::new(
-                input,
-                nom::error::ErrorKind::OneOf,
-            ))),
-        }
+    println!("\nOptimal score: {}", plan.score);
+    for (part, score, allowed) in gaps {
+        let label = LABELS[part as usize - 1];
+        let gap = match plan.score - score {
+            gap if gap >= 0 => format!("{} short of optimal", gap),
+            gap => format!("{} over optimal", -gap),
+        };
+        let note = if allowed {
+            ""
+        } else {
+            ", breaks the constraints"
+        };
+        println!("{}: {}, {}{}", label, score, gap, note);
     }
+    println!("Responses: {}", responses);
+}
 
-    fn response_for_outcome(&self, outcome: GameOutcome) -> Self {
-        match outcome {
-            GameOutcome::Tie => *self,
-            GameOutcome::Win => match self {
-                PlayChoice::Rock => PlayChoice::Paper,
-                PlayChoice::Paper => PlayChoice::Scissors,
-                PlayChoice::Scissors => PlayChoice::Rock,
-            },
-            GameOutcome::Lose => match self {
-                PlayChoice::Rock => PlayChoice::Scissors,
-                PlayChoice::Paper => PlayChoice::Rock,
-                PlayChoice::Scissors => PlayChoice::Paper,
-            }
+// Every built-in strategy, and the readings of the guide that parse and the opponent's column
+// replayed.
+fn players(guide: &Guide, seed: u64) -> Vec<Box<dyn Player>> {
+    let mut players: Vec<Box<dyn Player>> = vec![
+        Box::new(Fixed(PlayChoice::Rock)),
+        Box::new(Fixed(PlayChoice::Paper)),
+        Box::new(Fixed(PlayChoice::Scissors)),
+        Box::new(Random::new(seed)),
+        Box::new(FrequencyCounter::default()),
+        Box::new(WinStayLoseShift::default()),
+    ];
+
+    let responses = |games: Result<&[(PlayChoice, PlayChoice)], _>| {
+        games.map_or(vec![], |games| games.iter().map(|(_, b)| *b).collect())
+    };
+    let columns = [
+        ("guide", responses(guide.games())),
+        ("guide v2", responses(guide.games_v2())),
+        ("opponent", guide.opponents()),
+    ];
+    for (name, moves) in columns {
+        if let Some(replay) = GuideReplay::new(name, moves) {
+            players.push(Box::new(replay));
         }
     }
+    players
 }
 
-fn parse_games(input: &str) -> IResult<&str, Vec<(PlayChoice, PlayChoice)>> {
-    separated_list1(newline, PlayChoice::parse_pair)(input)
-}
-
-fn parse_games_v2(input: &str) -> IResult<&str, Vec<(PlayChoice, PlayChoice)>> {
-    separated_list1(newline, PlayChoice::parse_pair_v2)(input)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn total_score() {
-        let input = include_str!("./sample.txt");
-
-        let (res, games) = parse_games(input).unwrap();
-        assert_eq!(res, "");
-        assert_eq!(games.len(), 3);
-        assert_eq!(games[0], (PlayChoice::Rock, PlayChoice::Paper));
-
-        let total_score = games.iter().map(|(a, b)| b.game_score(a)).sum::<i32>();
-        assert_eq!(total_score, 15);
+fn letter(choice: &PlayChoice) -> char {
+    match choice {
+        PlayChoice::Rock => 'R',
+        PlayChoice::Paper => 'P',
+        PlayChoice::Scissors => 'S',
     }
+}
 
-    #[test]
-    fn total_score_2() {
-        let input = include_str!("./sample.txt");
-
-        let (res, games) = parse_games_v2(input).unwrap();
-        assert_eq!(res, "");
-        assert_eq!(games.len(), 3);
-        assert_eq!(games[0], (PlayChoice::Rock, PlayChoice::Rock));
-
-        let total_score = games.iter().map(|(a, b)| b.game_score(a)).sum::<i32>();
-        assert_eq!(total_score, 12);
+fn print_explanation(part: u8, explanation: &Explanation, json: bool) {
+    if json {
+        for mut line in explanation.json_lines() {
+            line["part"] = part.into();
+            println!("{}", line);
+        }
+    } else {
+        println!("\n{}:\n{}", LABELS[part as usize - 1], explanation);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,218 @@
+//! Which tokens of a strategy guide stand for which moves and outcomes.
+//!
+//! A mapping is configured in TOML, with one optional table per column; missing tables
+//! keep the puzzle's symbols:
+//!
+//! ```toml
+//! [opponent]
+//! rock = "rock"
+//! paper = "paper"
+//! scissors = "scissors"
+//!
+//! # The second column, read as our move (part 1).
+//! [response]
+//! X = "rock"
+//! Y = "paper"
+//! Z = "scissors"
+//!
+//! # The second column, read as the outcome to reach (part 2).
+//! [outcome]
+//! X = "lose"
+//! Y = "tie"
+//! Z = "win"
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use nom::bytes::complete::take_till1;
+use nom::error::{Error, ErrorKind};
+use nom::IResult;
+
+use super::{GameOutcome, PlayChoice, RuleTable};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolMapping {
+    opponent: HashMap<String, PlayChoice>,
+    response: HashMap<String, PlayChoice>,
+    outcome: HashMap<String, GameOutcome>,
+}
+
+impl SymbolMapping {
+    /// The symbols of the puzzle: `A`/`B`/`C` then `X`/`Y`/`Z`.
+    pub fn classic() -> &'static Self {
+        static CLASSIC: OnceLock<SymbolMapping> = OnceLock::new();
+        CLASSIC.get_or_init(|| {
+            let shapes = [PlayChoice::Rock, PlayChoice::Paper, PlayChoice::Scissors];
+            let outcomes = [GameOutcome::Lose, GameOutcome::Tie, GameOutcome::Win];
+            SymbolMapping {
+                opponent: symbols(["A", "B", "C"], shapes),
+                response: symbols(["X", "Y", "Z"], shapes),
+                outcome: symbols(["X", "Y", "Z"], outcomes),
+            }
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let input = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        SymbolMapping::parse(&input).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let table: toml::Table = input.parse().map_err(|e| format!("{}", e))?;
+        let classic = SymbolMapping::classic();
+
+        for key in table.keys() {
+            if !["opponent", "response", "outcome"].contains(&key.as_str()) {
+                return Err(format!("Unknown column `{}`", key));
+            }
+        }
+
+        Ok(SymbolMapping {
+            opponent: column(&table, "opponent", parse_shape)?
+                .unwrap_or_else(|| classic.opponent.clone()),
+            response: column(&table, "response", parse_shape)?
+                .unwrap_or_else(|| classic.response.clone()),
+            outcome: column(&table, "outcome", parse_outcome)?
+                .unwrap_or_else(|| classic.outcome.clone()),
+        })
+    }
+
+    /// Parses a token of the first column.
+    pub fn opponent<'a>(&self, input: &'a str) -> IResult<&'a str, PlayChoice> {
+        token(&self.opponent, input)
+    }
+
+    /// Parses a token of the second column as a move.
+    pub fn response<'a>(&self, input: &'a str) -> IResult<&'a str, PlayChoice> {
+        token(&self.response, input)
+    }
+
+    /// Parses a token of the second column as an outcome.
+    pub fn outcome<'a>(&self, input: &'a str) -> IResult<&'a str, GameOutcome> {
+        token(&self.outcome, input)
+    }
+}
+
+fn symbols<T: Copy>(tokens: [&str; 3], values: [T; 3]) -> HashMap<String, T> {
+    tokens.iter().map(|t| t.to_string()).zip(values).collect()
+}
+
+fn column<T>(
+    table: &toml::Table,
+    name: &str,
+    parse: fn(&str) -> Option<T>,
+) -> Result<Option<HashMap<String, T>>, String> {
+    let column = match table.get(name) {
+        Some(column) => column
+            .as_table()
+            .ok_or(format!("`{}` must be a table", name))?,
+        None => return Ok(None),
+    };
+
+    let mut symbols = HashMap::new();
+    for (token, value) in column {
+        if token.is_empty() || token.contains(char::is_whitespace) {
+            return Err(format!("Invalid token `{}` in `{}`", token, name));
+        }
+        let value = value
+            .as_str()
+            .and_then(parse)
+            .ok_or(format!("Invalid value for `{}` in `{}`", token, name))?;
+        symbols.insert(token.clone(), value);
+    }
+
+    if symbols.is_empty() {
+        return Err(format!("`{}` has no tokens", name));
+    }
+    Ok(Some(symbols))
+}
+
+fn parse_shape(name: &str) -> Option<PlayChoice> {
+    RuleTable::classic()
+        .find(name)
+        .and_then(PlayChoice::from_move)
+}
+
+fn parse_outcome(name: &str) -> Option<GameOutcome> {
+    match name.to_ascii_lowercase().as_str() {
+        "lose" => Some(GameOutcome::Lose),
+        "tie" => Some(GameOutcome::Tie),
+        "win" => Some(GameOutcome::Win),
+        _ => None,
+    }
+}
+
+// A run of non-whitespace characters, which must be one of `symbols`.
+fn token<'a, T: Copy>(symbols: &HashMap<String, T>, input: &'a str) -> IResult<&'a str, T> {
+    let (rest, token) = take_till1(char::is_whitespace)(input)?;
+    match symbols.get(token) {
+        Some(value) => Ok((rest, *value)),
+        None => Err(nom::Err::Error(Error::new(input, ErrorKind::OneOf))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse_games_v2_with, parse_games_with, parse_with, Day02};
+    use super::*;
+    use crate::error::parse_all;
+    use crate::{Answer, Solution};
+
+    const WORDS: &str = "
+[opponent]
+rock = \"rock\"
+paper = \"paper\"
+scissors = \"scissors\"
+
+[response]
+rock = \"rock\"
+paper = \"paper\"
+scissors = \"scissors\"
+";
+
+    #[test]
+    fn parses_guides_with_words() {
+        let mapping = SymbolMapping::parse(WORDS).unwrap();
+        let guide = "rock paper\npaper rock\nscissors scissors\n";
+
+        let games = parse_all(guide, |s| parse_games_with(s, &mapping)).unwrap();
+        assert_eq!(games[0], (PlayChoice::Rock, PlayChoice::Paper));
+        assert_eq!(super::super::total_score(&games), 15);
+
+        // The outcome column was not configured, so it keeps X/Y/Z.
+        let games = parse_all("rock Y\npaper X", |s| parse_games_v2_with(s, &mapping)).unwrap();
+        assert_eq!(games[1], (PlayChoice::Paper, PlayChoice::Rock));
+
+        assert!(parse_all("rock lizard", |s| parse_games_with(s, &mapping)).is_err());
+    }
+
+    #[test]
+    fn solves_guides_with_words() {
+        let mapping = SymbolMapping::parse(WORDS).unwrap();
+        let guide = parse_with("rock paper\npaper rock\nscissors scissors\n", &mapping).unwrap();
+
+        assert_eq!(Day02::part1(&guide), Ok(Answer::Number(15)));
+        // `paper` is a response but not an outcome, so only part 2 fails.
+        assert!(matches!(
+            Day02::part2(&guide),
+            Err(crate::Error::Parse {
+                line: 1,
+                column: 6,
+                ..
+            })
+        ));
+        assert!(parse_with("lizard paper", &mapping).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_mappings() {
+        assert!(SymbolMapping::parse("[opponent]\nA = \"lizard\"").is_err());
+        assert!(SymbolMapping::parse("[outcome]\nX = \"rock\"").is_err());
+        assert!(SymbolMapping::parse("[outcome]").is_err());
+        assert!(SymbolMapping::parse("[third]\nA = \"rock\"").is_err());
+        assert_eq!(SymbolMapping::parse("").unwrap(), *SymbolMapping::classic());
+    }
+}
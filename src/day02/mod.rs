@@ -0,0 +1,302 @@
+//! Day 2: Rock Paper Scissors.
+
+use nom::character::complete::{newline, space1};
+use nom::IResult;
+use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+
+use crate::error::parse_all;
+use crate::{Answer, Result, Solution};
+
+mod explain;
+mod interpret;
+mod mapping;
+mod optimal;
+mod rules;
+mod tournament;
+
+pub use explain::{Explanation, RoundTrace};
+pub use interpret::{parse_guide_with, Comparison, GuideRound, Interpretation, Reading};
+pub use mapping::SymbolMapping;
+pub use optimal::{optimal_plan, Constraints, Plan};
+pub use rules::{Move, RuleTable};
+pub use tournament::{
+    play_match, round_robin, Fixed, FrequencyCounter, GuideReplay, Leaderboard, Player, Random,
+    Standing, WinStayLoseShift,
+};
+
+/// Scores the strategy guide, reading the second column as a move (part 1) or an outcome (part 2).
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Guide;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_with(input, SymbolMapping::classic())
+    }
+
+    fn part1(guide: &Self::Input) -> Result<Answer> {
+        Ok(total_score(guide.games()?).into())
+    }
+
+    fn part2(guide: &Self::Input) -> Result<Answer> {
+        Ok(total_score(guide.games_v2()?).into())
+    }
+}
+
+/// The strategy guide read both ways. Each reading fails on its own, so a guide whose second
+/// column only holds responses (or only outcomes) can still be scored for the other part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guide {
+    games: Result<Vec<(PlayChoice, PlayChoice)>>,
+    games_v2: Result<Vec<(PlayChoice, PlayChoice)>>,
+    outcomes: Result<Vec<(PlayChoice, GameOutcome)>>,
+}
+
+impl Guide {
+    /// The `(opponent, response)` rounds, reading the second column as a response (part 1).
+    pub fn games(&self) -> Result<&[(PlayChoice, PlayChoice)]> {
+        self.games.as_deref().map_err(Clone::clone)
+    }
+
+    /// The `(opponent, response)` rounds, reading the second column as an outcome (part 2).
+    pub fn games_v2(&self) -> Result<&[(PlayChoice, PlayChoice)]> {
+        self.games_v2.as_deref().map_err(Clone::clone)
+    }
+
+    /// The `(opponent, outcome)` rounds the responses of [`Guide::games_v2`] are picked from.
+    pub fn outcomes(&self) -> Result<&[(PlayChoice, GameOutcome)]> {
+        self.outcomes.as_deref().map_err(Clone::clone)
+    }
+
+    /// The opponent's moves, which both readings share.
+    pub fn opponents(&self) -> Vec<PlayChoice> {
+        let games = self
+            .games()
+            .or_else(|_| self.games_v2())
+            .unwrap_or_default();
+        games.iter().map(|(opponent, _)| *opponent).collect()
+    }
+}
+
+/// Parses the strategy guide once and reads it both ways, reading its symbols with `mapping`.
+///
+/// Fails only if neither reading works, with the error of the first one.
+pub fn parse_with(input: &str, mapping: &SymbolMapping) -> Result<Guide> {
+    let rounds = parse_all(input, |s| parse_guide_with(s, mapping))?;
+    let games = interpret::read_rounds(input, &rounds, |r| r.as_move(mapping));
+    let outcomes = interpret::read_rounds(input, &rounds, |r| {
+        r.outcome(mapping).map(|outcome| (r.opponent, outcome))
+    });
+    let games_v2 = outcomes.as_ref().map_err(Clone::clone).map(|outcomes| {
+        let games = outcomes.iter();
+        games
+            .map(|&(a, outcome)| (a, a.response_for_outcome(outcome)))
+            .collect()
+    });
+    match (games, games_v2, outcomes) {
+        (Err(e), Err(_), _) => Err(e),
+        (games, games_v2, outcomes) => Ok(Guide {
+            games,
+            games_v2,
+            outcomes,
+        }),
+    }
+}
+
+/// The total score of playing every round.
+pub fn total_score(games: &[(PlayChoice, PlayChoice)]) -> i32 {
+    games.iter().map(|(a, b)| b.game_score(a)).sum::<i32>()
+}
+
+/// A shape of the classic game, scored and compared by [`RuleTable::classic`].
+#[derive(Copy, Debug, Clone, PartialEq)]
+pub enum PlayChoice {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+/// The result of a round, from the point of view of the player.
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GameOutcome {
+    Lose,
+    Tie,
+    Win,
+}
+
+impl GameOutcome {
+    /// The score of the outcome in the classic game.
+    pub fn score(&self) -> i32 {
+        RuleTable::classic().outcome_score(*self)
+    }
+
+    /// Parses `X`, `Y` or `Z` as lose, tie or win.
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        SymbolMapping::classic().outcome(input)
+    }
+}
+
+impl PlayChoice {
+    /// Every shape, in the order of their moves in [`RuleTable::classic`].
+    pub const ALL: [PlayChoice; 3] = [PlayChoice::Rock, PlayChoice::Paper, PlayChoice::Scissors];
+
+    /// The score of playing `self` against `other`: shape score plus outcome score.
+    pub fn game_score(&self, other: &Self) -> i32 {
+        self.game(other).score() + self.score()
+    }
+
+    pub fn score(&self) -> i32 {
+        RuleTable::classic().move_score(self.to_move())
+    }
+
+    /// The outcome of playing `self` against `other`.
+    pub fn game(&self, other: &Self) -> GameOutcome {
+        RuleTable::classic().outcome(self.to_move(), other.to_move())
+    }
+
+    /// The move of [`RuleTable::classic`] for this shape.
+    pub fn to_move(self) -> Move {
+        match self {
+            PlayChoice::Rock => Move(0),
+            PlayChoice::Paper => Move(1),
+            PlayChoice::Scissors => Move(2),
+        }
+    }
+
+    /// The shape of a move of [`RuleTable::classic`], or `None` for a move of a larger table.
+    pub fn from_move(m: Move) -> Option<Self> {
+        match m.0 {
+            0 => Some(PlayChoice::Rock),
+            1 => Some(PlayChoice::Paper),
+            2 => Some(PlayChoice::Scissors),
+            _ => None,
+        }
+    }
+
+    /// Parses a round as `<opponent> <response>`.
+    pub fn parse_pair(input: &str) -> IResult<&str, (Self, Self)> {
+        PlayChoice::parse_pair_with(input, SymbolMapping::classic())
+    }
+
+    /// Parses a round as `<opponent> <outcome>` and picks the response reaching that outcome.
+    pub fn parse_pair_v2(input: &str) -> IResult<&str, (Self, Self)> {
+        PlayChoice::parse_pair_v2_with(input, SymbolMapping::classic())
+    }
+
+    /// Like [`PlayChoice::parse_pair`], reading the symbols with `mapping`.
+    pub fn parse_pair_with<'a>(
+        input: &'a str,
+        mapping: &SymbolMapping,
+    ) -> IResult<&'a str, (Self, Self)> {
+        separated_pair(|s| mapping.opponent(s), space1, |s| mapping.response(s))(input)
+    }
+
+    /// Like [`PlayChoice::parse_pair_v2`], reading the symbols with `mapping`.
+    pub fn parse_pair_v2_with<'a>(
+        input: &'a str,
+        mapping: &SymbolMapping,
+    ) -> IResult<&'a str, (Self, Self)> {
+        let (input, (a, outcome)) = parse_outcome_with(input, mapping)?;
+
+        let b = a.response_for_outcome(outcome);
+
+        Ok((input, (a, b)))
+    }
+
+    /// Parses the opponent column: `A`, `B` or `C`.
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        SymbolMapping::classic().opponent(input)
+    }
+
+    /// Parses the response column: `X`, `Y` or `Z`.
+    pub fn parse_second(input: &str) -> IResult<&str, Self> {
+        SymbolMapping::classic().response(input)
+    }
+
+    /// The shape to play against `self` to reach `outcome`.
+    pub fn response_for_outcome(&self, outcome: GameOutcome) -> Self {
+        // Every outcome can be reached against every shape of the classic game.
+        let response = RuleTable::classic().response_for_outcome(self.to_move(), outcome);
+        response.and_then(PlayChoice::from_move).unwrap()
+    }
+}
+
+/// Parses the strategy guide as `(opponent, response)` rounds.
+pub fn parse_games(input: &str) -> IResult<&str, Vec<(PlayChoice, PlayChoice)>> {
+    parse_games_with(input, SymbolMapping::classic())
+}
+
+/// Parses the strategy guide as `(opponent, response)` rounds, reading the second column as an outcome.
+pub fn parse_games_v2(input: &str) -> IResult<&str, Vec<(PlayChoice, PlayChoice)>> {
+    parse_games_v2_with(input, SymbolMapping::classic())
+}
+
+/// Parses the strategy guide as `(opponent, outcome)` rounds.
+pub fn parse_outcomes(input: &str) -> IResult<&str, Vec<(PlayChoice, GameOutcome)>> {
+    parse_outcomes_with(input, SymbolMapping::classic())
+}
+
+/// Like [`parse_outcomes`], reading the symbols with `mapping`.
+pub fn parse_outcomes_with<'a>(
+    input: &'a str,
+    mapping: &SymbolMapping,
+) -> IResult<&'a str, Vec<(PlayChoice, GameOutcome)>> {
+    separated_list1(newline, |s| parse_outcome_with(s, mapping))(input)
+}
+
+fn parse_outcome_with<'a>(
+    input: &'a str,
+    mapping: &SymbolMapping,
+) -> IResult<&'a str, (PlayChoice, GameOutcome)> {
+    separated_pair(|s| mapping.opponent(s), space1, |s| mapping.outcome(s))(input)
+}
+
+/// Like [`parse_games`], reading the symbols with `mapping`.
+pub fn parse_games_with<'a>(
+    input: &'a str,
+    mapping: &SymbolMapping,
+) -> IResult<&'a str, Vec<(PlayChoice, PlayChoice)>> {
+    separated_list1(newline, |s| PlayChoice::parse_pair_with(s, mapping))(input)
+}
+
+/// Like [`parse_games_v2`], reading the symbols with `mapping`.
+pub fn parse_games_v2_with<'a>(
+    input: &'a str,
+    mapping: &SymbolMapping,
+) -> IResult<&'a str, Vec<(PlayChoice, PlayChoice)>> {
+    separated_list1(newline, |s| PlayChoice::parse_pair_v2_with(s, mapping))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_score() {
+        let input = include_str!("./sample.txt");
+
+        let (res, games) = parse_games(input).unwrap();
+        assert_eq!(res, "");
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[0], (PlayChoice::Rock, PlayChoice::Paper));
+
+        let total_score = games.iter().map(|(a, b)| b.game_score(a)).sum::<i32>();
+        assert_eq!(total_score, 15);
+    }
+
+    #[test]
+    fn total_score_2() {
+        let input = include_str!("./sample.txt");
+
+        let (res, games) = parse_games_v2(input).unwrap();
+        assert_eq!(res, "");
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[0], (PlayChoice::Rock, PlayChoice::Rock));
+
+        let total_score = games.iter().map(|(a, b)| b.game_score(a)).sum::<i32>();
+        assert_eq!(total_score, 12);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,307 @@
+//! The best score reachable against a known sequence of opponent moves.
+
+use std::collections::VecDeque;
+
+use super::{GameOutcome, PlayChoice};
+
+/// Limits on the responses a plan may use.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Constraints {
+    /// At most this many rounds may be won.
+    pub max_wins: Option<usize>,
+    /// The same shape may not be played more than this many rounds in a row.
+    pub max_repeat: Option<usize>,
+}
+
+impl Constraints {
+    /// Whether playing `responses` against `opponents` respects the constraints.
+    pub fn allows(&self, opponents: &[PlayChoice], responses: &[PlayChoice]) -> bool {
+        let wins = opponents
+            .iter()
+            .zip(responses)
+            .filter(|(a, b)| b.game(a) == GameOutcome::Win)
+            .count();
+        let longest_run = responses
+            .chunk_by(|a, b| a == b)
+            .map(<[PlayChoice]>::len)
+            .max()
+            .unwrap_or(0);
+
+        self.max_wins.is_none_or(|n| wins <= n) && self.max_repeat.is_none_or(|k| longest_run <= k)
+    }
+}
+
+/// The responses to play, round by round, and the score they reach.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    pub responses: Vec<PlayChoice>,
+    pub score: i32,
+}
+
+/// Finds the responses scoring the most against `opponents` under `constraints`, scored with
+/// [`PlayChoice::game_score`], or `None` if the constraints cannot be met.
+///
+/// A dynamic program over the rounds, whose states are the number of wins so far (only when
+/// limited) and the last response. Runs in O(rounds × (max_wins + 1)) time and memory, whatever
+/// `max_repeat` is.
+pub fn optimal_plan(opponents: &[PlayChoice], constraints: &Constraints) -> Option<Plan> {
+    if constraints.max_repeat == Some(0) && !opponents.is_empty() {
+        return None;
+    }
+
+    // The best plan without the win limit is the best plan with it, if it happens to respect it.
+    let plan = solve(opponents, None, constraints.max_repeat);
+    match constraints.max_wins {
+        Some(max_wins) if wins(opponents, &plan.responses) > max_wins => {
+            Some(solve(opponents, Some(max_wins), constraints.max_repeat))
+        }
+        _ => Some(plan),
+    }
+}
+
+fn wins(opponents: &[PlayChoice], responses: &[PlayChoice]) -> usize {
+    let rounds = opponents.iter().zip(responses);
+    rounds
+        .filter(|(a, b)| b.game(a) == GameOutcome::Win)
+        .count()
+}
+
+// No run before the first round.
+const NO_RUN: u32 = u32::MAX;
+
+/// The plans ending with a run of the same shape, as `(first round of the run, score)`, from the
+/// longest run to the shortest. Only runs scoring more than every shorter one are kept, since
+/// they are the only ones that can ever be the best: the first one is the best.
+#[derive(Debug, Clone, Default)]
+struct Runs(VecDeque<(usize, i64)>);
+
+impl Runs {
+    fn best(&self) -> Option<(usize, i64)> {
+        self.0.front().copied()
+    }
+
+    fn push(&mut self, start: usize, score: i64) {
+        while self.0.back().is_some_and(|&(_, s)| s <= score) {
+            self.0.pop_back();
+        }
+        self.0.push_back((start, score));
+    }
+
+    // Drops the runs that would be longer than `window` if they went on in `round`.
+    fn expire(&mut self, round: usize, window: usize) {
+        while self
+            .0
+            .front()
+            .is_some_and(|&(start, _)| start + window <= round)
+        {
+            self.0.pop_front();
+        }
+    }
+}
+
+fn solve(opponents: &[PlayChoice], max_wins: Option<usize>, max_repeat: Option<usize>) -> Plan {
+    let rounds = opponents.len();
+    let win_rows = max_wins.map_or(1, |n| n.min(rounds) + 1);
+    let window = max_repeat.map_or(rounds, |k| k.min(rounds));
+    let won = |shape: usize, round: usize| {
+        max_wins.is_some() && PlayChoice::ALL[shape].game(&opponents[round]) == GameOutcome::Win
+    };
+
+    // runs[shape][wins]: the plans so far ending with `shape` and winning `wins` rounds. Every
+    // plan ending with a shape gains the same score when it goes on with it, so its scores are
+    // stored relative to offsets[shape].
+    let mut runs: [VecDeque<Runs>; 3] =
+        std::array::from_fn(|_| vec![Runs::default(); win_rows].into());
+    let mut offsets = [0i64; 3];
+    // from[round][wins * 3 + shape]: the run before the one of `shape` starting at `round` after
+    // `wins` wins, as `start * 3 + shape`.
+    let mut from: Vec<Vec<u32>> = Vec::with_capacity(rounds);
+
+    for (i, opponent) in opponents.iter().enumerate() {
+        let best: Vec<[Option<(usize, i64)>; 3]> = (0..win_rows)
+            .map(|w| {
+                std::array::from_fn(|m| runs[m][w].best().map(|(s, score)| (s, score + offsets[m])))
+            })
+            .collect();
+
+        let reachable = i.min(win_rows - 1) + 1;
+        let mut previous = vec![NO_RUN; reachable * 3];
+        for (m, shape) in PlayChoice::ALL.iter().enumerate() {
+            let gain = shape.game_score(opponent) as i64;
+            let won = won(m, i) as usize;
+
+            // Go on with every run of the shape.
+            if won == 1 {
+                runs[m].pop_back();
+                runs[m].push_front(Runs::default());
+            }
+            offsets[m] += gain;
+            for row in runs[m].iter_mut() {
+                row.expire(i, window);
+            }
+
+            // Or start a run after the best run of another shape.
+            for w in 0..reachable.min(win_rows - won) {
+                let before = if i == 0 {
+                    Some((NO_RUN, 0))
+                } else {
+                    (0..3)
+                        .filter(|&p| p != m)
+                        .filter_map(|p| best[w][p].map(|(s, score)| ((s * 3 + p) as u32, score)))
+                        .fold(None, |acc, (run, score)| match acc {
+                            Some((_, best)) if best >= score => acc,
+                            _ => Some((run, score)),
+                        })
+                };
+                if let Some((run, score)) = before {
+                    runs[m][w + won].push(i, score + gain - offsets[m]);
+                    previous[w * 3 + m] = run;
+                }
+            }
+        }
+        from.push(previous);
+    }
+
+    let mut last = None;
+    for (m, rows) in runs.iter().enumerate() {
+        for (w, row) in rows.iter().enumerate() {
+            if let Some((start, score)) = row.best() {
+                let score = score + offsets[m];
+                if last.is_none_or(|(_, _, _, best)| score > best) {
+                    last = Some((m, w, start, score));
+                }
+            }
+        }
+    }
+
+    let mut responses = vec![PlayChoice::Rock; rounds];
+    let Some((mut m, mut w, mut start, score)) = last else {
+        return Plan {
+            responses,
+            score: 0,
+        };
+    };
+    let mut end = rounds;
+    loop {
+        for (j, response) in responses.iter_mut().enumerate().take(end).skip(start) {
+            *response = PlayChoice::ALL[m];
+            w -= won(m, j) as usize;
+        }
+        if start == 0 {
+            break;
+        }
+        let run = from[start][w * 3 + m] as usize;
+        (end, start, m) = (start, run / 3, run % 3);
+    }
+
+    Plan {
+        responses,
+        score: score as i32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Player, Random};
+    use super::*;
+    use PlayChoice::*;
+
+    #[test]
+    fn wins_every_round_without_constraints() {
+        let opponents = [Rock, Paper, Scissors];
+        let plan = optimal_plan(&opponents, &Constraints::default()).unwrap();
+
+        assert_eq!(plan.responses, vec![Paper, Scissors, Rock]);
+        assert_eq!(plan.score, 24);
+
+        let one_win = Constraints {
+            max_wins: Some(1),
+            max_repeat: None,
+        };
+        let plan = optimal_plan(&opponents, &one_win).unwrap();
+        assert_eq!(plan.score, 19);
+        assert!(one_win.allows(&opponents, &plan.responses));
+    }
+
+    #[test]
+    fn limits_repeated_responses() {
+        let opponents = [Rock, Rock, Rock];
+        let no_repeat = Constraints {
+            max_wins: None,
+            max_repeat: Some(1),
+        };
+
+        let plan = optimal_plan(&opponents, &no_repeat).unwrap();
+        assert_eq!(plan.responses, vec![Paper, Rock, Paper]);
+        assert_eq!(plan.score, 20);
+        assert!(!no_repeat.allows(&opponents, &[Paper, Paper, Rock]));
+
+        let never = Constraints {
+            max_wins: None,
+            max_repeat: Some(0),
+        };
+        assert!(optimal_plan(&opponents, &never).is_none());
+        assert_eq!(optimal_plan(&[], &never).unwrap().score, 0);
+    }
+
+    fn score(opponents: &[PlayChoice], responses: &[PlayChoice]) -> i32 {
+        let rounds = opponents.iter().zip(responses);
+        rounds.map(|(a, b)| b.game_score(a)).sum()
+    }
+
+    // Every response sequence, for checking small cases exhaustively.
+    fn all_plans(rounds: usize) -> Vec<Vec<PlayChoice>> {
+        (0..3usize.pow(rounds as u32))
+            .map(|mut n| {
+                (0..rounds)
+                    .map(|_| {
+                        let shape = PlayChoice::ALL[n % 3];
+                        n /= 3;
+                        shape
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_exhaustive_search() {
+        let opponents = [Rock, Rock, Paper, Rock, Scissors, Scissors, Rock];
+        let plans = all_plans(opponents.len());
+
+        for max_wins in [None, Some(0), Some(2), Some(4)] {
+            for max_repeat in [None, Some(1), Some(2), Some(3)] {
+                let constraints = Constraints {
+                    max_wins,
+                    max_repeat,
+                };
+                let best = plans
+                    .iter()
+                    .filter(|plan| constraints.allows(&opponents, plan))
+                    .map(|plan| score(&opponents, plan))
+                    .max();
+
+                let plan = optimal_plan(&opponents, &constraints).unwrap();
+                assert_eq!(Some(plan.score), best, "{:?}", constraints);
+                assert!(constraints.allows(&opponents, &plan.responses));
+            }
+        }
+    }
+
+    #[test]
+    fn handles_large_constraints() {
+        let mut random = Random::new(1);
+        let opponents: Vec<PlayChoice> = (0..1500).map(|i| random.play(i)).collect();
+
+        // Tracking every run length would take 1500 × 401 × 3 × 1500 states.
+        for (max_wins, max_repeat) in [(400, 1500), (usize::MAX, 2), (10, usize::MAX)] {
+            let constraints = Constraints {
+                max_wins: Some(max_wins),
+                max_repeat: Some(max_repeat),
+            };
+            let plan = optimal_plan(&opponents, &constraints).unwrap();
+            assert!(constraints.allows(&opponents, &plan.responses));
+            assert_eq!(plan.score, score(&opponents, &plan.responses));
+        }
+    }
+}
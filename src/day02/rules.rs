@@ -0,0 +1,215 @@
+//! Rule tables describing Rock Paper Scissors and its N-move variants.
+
+use std::sync::OnceLock;
+
+use crate::{Error, Result};
+
+use super::GameOutcome;
+
+/// A move of a [`RuleTable`], identified by its index in the table.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Move(pub usize);
+
+/// The moves of a game, which move beats which, and how rounds are scored.
+///
+/// A round is worth the score of the move played plus the score of its outcome.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleTable {
+    names: Vec<String>,
+    scores: Vec<i32>,
+    /// `beats[a][b]` is true when `a` beats `b`.
+    beats: Vec<Vec<bool>>,
+    /// The scores of losing, tying and winning a round.
+    outcome_scores: [i32; 3],
+}
+
+impl RuleTable {
+    /// Builds a table from `(move, score)` pairs and `(winner, loser)` pairs of move indices.
+    ///
+    /// Fails if a move beats itself, or if two moves beat each other.
+    pub fn new(
+        moves: &[(&str, i32)],
+        beats: &[(usize, usize)],
+        outcome_scores: [i32; 3],
+    ) -> Result<Self> {
+        let n = moves.len();
+        if n == 0 {
+            return Err(Error::InvalidRules("no moves".to_string()));
+        }
+
+        let mut table = vec![vec![false; n]; n];
+        for &(winner, loser) in beats {
+            if winner >= n || loser >= n {
+                return Err(Error::InvalidRules(format!(
+                    "move {} does not exist",
+                    winner.max(loser)
+                )));
+            }
+            if winner == loser || table[loser][winner] {
+                return Err(Error::InvalidRules(format!(
+                    "{} and {} cannot beat each other",
+                    moves[winner].0, moves[loser].0
+                )));
+            }
+            table[winner][loser] = true;
+        }
+
+        Ok(RuleTable {
+            names: moves.iter().map(|(name, _)| name.to_string()).collect(),
+            scores: moves.iter().map(|(_, score)| *score).collect(),
+            beats: table,
+            outcome_scores,
+        })
+    }
+
+    /// A balanced cyclic game with an odd number of moves, scored 1, 2, 3... in order, where
+    /// every move beats the moves an odd number of steps before it, e.g. paper beats rock.
+    pub fn cyclic(names: &[&str]) -> Result<Self> {
+        let n = names.len();
+        if n.is_multiple_of(2) {
+            return Err(Error::InvalidRules(format!(
+                "a cyclic game needs an odd number of moves, got {}",
+                n
+            )));
+        }
+
+        let moves: Vec<(&str, i32)> = names.iter().zip(1..).map(|(&m, s)| (m, s)).collect();
+        let beats: Vec<(usize, usize)> = (0..n)
+            .flat_map(|a| (0..n).map(move |b| (a, b)))
+            .filter(|&(a, b)| (a + n - b) % n % 2 == 1)
+            .collect();
+
+        RuleTable::new(&moves, &beats, [0, 3, 6])
+    }
+
+    /// Rock, paper and scissors, as scored by the puzzle.
+    pub fn classic() -> &'static Self {
+        static CLASSIC: OnceLock<RuleTable> = OnceLock::new();
+        CLASSIC.get_or_init(|| RuleTable::cyclic(&["rock", "paper", "scissors"]).unwrap())
+    }
+
+    /// Rock, paper, scissors, Spock and lizard.
+    pub fn rpsls() -> Self {
+        RuleTable::cyclic(&["rock", "paper", "scissors", "spock", "lizard"]).unwrap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        (0..self.len()).map(Move)
+    }
+
+    pub fn name(&self, m: Move) -> &str {
+        &self.names[m.0]
+    }
+
+    /// Looks up a move by name, ignoring case.
+    pub fn find(&self, name: &str) -> Option<Move> {
+        self.names
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(name))
+            .map(Move)
+    }
+
+    pub fn move_score(&self, m: Move) -> i32 {
+        self.scores[m.0]
+    }
+
+    pub fn outcome_score(&self, outcome: GameOutcome) -> i32 {
+        self.outcome_scores[outcome as usize]
+    }
+
+    /// The outcome of playing `ours` against `theirs`.
+    pub fn outcome(&self, ours: Move, theirs: Move) -> GameOutcome {
+        if self.beats[ours.0][theirs.0] {
+            GameOutcome::Win
+        } else if self.beats[theirs.0][ours.0] {
+            GameOutcome::Lose
+        } else {
+            GameOutcome::Tie
+        }
+    }
+
+    /// The score of playing `ours` against `theirs`: move score plus outcome score.
+    pub fn score(&self, ours: Move, theirs: Move) -> i32 {
+        self.move_score(ours) + self.outcome_score(self.outcome(ours, theirs))
+    }
+
+    /// The total score of `(theirs, ours)` rounds.
+    pub fn total_score(&self, rounds: &[(Move, Move)]) -> i32 {
+        rounds
+            .iter()
+            .map(|&(theirs, ours)| self.score(ours, theirs))
+            .sum()
+    }
+
+    /// The move to play against `theirs` to reach `outcome`, or `None` if no move does.
+    ///
+    /// When several moves reach it, the one scoring the most is picked, the first one on ties.
+    pub fn response_for_outcome(&self, theirs: Move, outcome: GameOutcome) -> Option<Move> {
+        self.moves()
+            .filter(|&m| self.outcome(m, theirs) == outcome)
+            .min_by_key(|&m| -self.move_score(m))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::PlayChoice;
+    use super::*;
+
+    #[test]
+    fn classic_rules() {
+        let rules = RuleTable::classic();
+        let (rock, paper, scissors) = (Move(0), Move(1), Move(2));
+
+        assert_eq!(rules.outcome(paper, rock), GameOutcome::Win);
+        assert_eq!(rules.outcome(rock, scissors), GameOutcome::Win);
+        assert_eq!(rules.outcome(rock, paper), GameOutcome::Lose);
+        assert_eq!(rules.outcome(scissors, scissors), GameOutcome::Tie);
+
+        // The sample: A Y, B X, C Z.
+        let rounds = [(rock, paper), (paper, rock), (scissors, scissors)];
+        assert_eq!(rules.total_score(&rounds), 15);
+
+        assert_eq!(
+            rules.response_for_outcome(rock, GameOutcome::Lose),
+            Some(scissors)
+        );
+        assert!(RuleTable::cyclic(&["a", "b"]).is_err());
+        assert!(RuleTable::new(&[("a", 1), ("b", 2)], &[(0, 1), (1, 0)], [0, 3, 6]).is_err());
+    }
+
+    #[test]
+    fn rock_paper_scissors_lizard_spock() {
+        let rules = RuleTable::rpsls();
+        let spock = rules.find("Spock").unwrap();
+        let lizard = rules.find("lizard").unwrap();
+
+        assert_eq!(rules.outcome(lizard, spock), GameOutcome::Win);
+        assert_eq!(
+            rules.outcome(spock, rules.find("scissors").unwrap()),
+            GameOutcome::Win
+        );
+        assert_eq!(PlayChoice::from_move(spock), None);
+        assert_eq!(PlayChoice::from_move(Move(1)), Some(PlayChoice::Paper));
+
+        for theirs in rules.moves() {
+            let wins = rules
+                .moves()
+                .filter(|&m| rules.outcome(m, theirs) == GameOutcome::Win);
+            assert_eq!(wins.count(), 2);
+
+            for outcome in [GameOutcome::Lose, GameOutcome::Tie, GameOutcome::Win] {
+                let ours = rules.response_for_outcome(theirs, outcome).unwrap();
+                assert_eq!(rules.outcome(ours, theirs), outcome);
+            }
+        }
+    }
+}
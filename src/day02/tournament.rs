@@ -0,0 +1,342 @@
+//! Round-robin tournaments between playing strategies.
+
+use std::cmp::{Ordering, Reverse};
+use std::fmt;
+
+use serde_json::{json, Value};
+
+use super::{GameOutcome, PlayChoice};
+
+/// A strategy taking part in matches.
+pub trait Player {
+    fn name(&self) -> String;
+
+    /// Forgets everything learned, before a new match.
+    fn reset(&mut self) {}
+
+    /// The shape to play in `round` of the match, from 0.
+    fn play(&mut self, round: usize) -> PlayChoice;
+
+    /// Learns what both players played in the last round.
+    fn observe(&mut self, _ours: PlayChoice, _theirs: PlayChoice) {}
+}
+
+/// Always plays the same shape.
+pub struct Fixed(pub PlayChoice);
+
+impl Player for Fixed {
+    fn name(&self) -> String {
+        format!("always {:?}", self.0).to_lowercase()
+    }
+
+    fn play(&mut self, _round: usize) -> PlayChoice {
+        self.0
+    }
+}
+
+/// Plays uniformly random shapes, the same sequence in every match for a given seed.
+pub struct Random {
+    seed: u64,
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Random { seed, state: seed }
+    }
+}
+
+impl Player for Random {
+    fn name(&self) -> String {
+        format!("random (seed {})", self.seed)
+    }
+
+    fn reset(&mut self) {
+        self.state = self.seed;
+    }
+
+    fn play(&mut self, _round: usize) -> PlayChoice {
+        // SplitMix64, which is good enough to pick shapes and needs no dependency.
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        PlayChoice::ALL[(z % 3) as usize]
+    }
+}
+
+/// Beats the shape the opponent has played the most so far, the first one among ties.
+#[derive(Default)]
+pub struct FrequencyCounter {
+    counts: [usize; 3],
+}
+
+impl Player for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency counter".to_string()
+    }
+
+    fn reset(&mut self) {
+        self.counts = [0; 3];
+    }
+
+    fn play(&mut self, _round: usize) -> PlayChoice {
+        let mut most = 0;
+        for (i, &count) in self.counts.iter().enumerate() {
+            if count > self.counts[most] {
+                most = i;
+            }
+        }
+        PlayChoice::ALL[most].response_for_outcome(GameOutcome::Win)
+    }
+
+    fn observe(&mut self, _ours: PlayChoice, theirs: PlayChoice) {
+        self.counts[theirs.to_move().0] += 1;
+    }
+}
+
+/// Starts with rock, keeps its shape after a win, and otherwise switches to the shape that
+/// beats the opponent's last one.
+pub struct WinStayLoseShift {
+    next: PlayChoice,
+}
+
+impl Default for WinStayLoseShift {
+    fn default() -> Self {
+        WinStayLoseShift {
+            next: PlayChoice::Rock,
+        }
+    }
+}
+
+impl Player for WinStayLoseShift {
+    fn name(&self) -> String {
+        "win-stay/lose-shift".to_string()
+    }
+
+    fn reset(&mut self) {
+        *self = WinStayLoseShift::default();
+    }
+
+    fn play(&mut self, _round: usize) -> PlayChoice {
+        self.next
+    }
+
+    fn observe(&mut self, ours: PlayChoice, theirs: PlayChoice) {
+        if ours.game(&theirs) != GameOutcome::Win {
+            self.next = theirs.response_for_outcome(GameOutcome::Win);
+        }
+    }
+}
+
+/// Replays a column of a strategy guide, starting over when the match is longer than it.
+pub struct GuideReplay {
+    name: String,
+    moves: Vec<PlayChoice>,
+}
+
+impl GuideReplay {
+    /// Returns `None` if there are no moves to replay.
+    pub fn new(name: &str, moves: Vec<PlayChoice>) -> Option<Self> {
+        if moves.is_empty() {
+            return None;
+        }
+        Some(GuideReplay {
+            name: name.to_string(),
+            moves,
+        })
+    }
+}
+
+impl Player for GuideReplay {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn play(&mut self, round: usize) -> PlayChoice {
+        self.moves[round % self.moves.len()]
+    }
+}
+
+/// Plays a match of `rounds` rounds and returns the total score of both players, every
+/// round scored with [`PlayChoice::game_score`].
+pub fn play_match(a: &mut dyn Player, b: &mut dyn Player, rounds: usize) -> (i64, i64) {
+    a.reset();
+    b.reset();
+
+    let (mut score_a, mut score_b) = (0, 0);
+    for round in 0..rounds {
+        let (shape_a, shape_b) = (a.play(round), b.play(round));
+        score_a += shape_a.game_score(&shape_b) as i64;
+        score_b += shape_b.game_score(&shape_a) as i64;
+        a.observe(shape_a, shape_b);
+        b.observe(shape_b, shape_a);
+    }
+    (score_a, score_b)
+}
+
+/// How a player did over all its matches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    /// Matches won, tied and lost, decided by the total score of the match.
+    pub wins: usize,
+    pub ties: usize,
+    pub losses: usize,
+    /// The sum of the scores of every match.
+    pub score: i64,
+}
+
+impl Standing {
+    /// 3 points per match won and 1 per match tied.
+    pub fn points(&self) -> usize {
+        3 * self.wins + self.ties
+    }
+}
+
+/// The standings of a tournament, best first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leaderboard {
+    pub rounds: usize,
+    pub standings: Vec<Standing>,
+}
+
+/// Plays a match of `rounds` rounds between every pair of players.
+///
+/// Players are ranked by points, then by score; remaining ties keep the order of `players`.
+pub fn round_robin(players: &mut [Box<dyn Player>], rounds: usize) -> Leaderboard {
+    let mut standings: Vec<Standing> = players
+        .iter()
+        .map(|p| Standing {
+            name: p.name(),
+            wins: 0,
+            ties: 0,
+            losses: 0,
+            score: 0,
+        })
+        .collect();
+
+    for i in 0..players.len() {
+        for j in i + 1..players.len() {
+            let (left, right) = players.split_at_mut(j);
+            let (a, b) = play_match(left[i].as_mut(), right[0].as_mut(), rounds);
+
+            standings[i].score += a;
+            standings[j].score += b;
+            match a.cmp(&b) {
+                Ordering::Greater => {
+                    standings[i].wins += 1;
+                    standings[j].losses += 1;
+                }
+                Ordering::Less => {
+                    standings[i].losses += 1;
+                    standings[j].wins += 1;
+                }
+                Ordering::Equal => {
+                    standings[i].ties += 1;
+                    standings[j].ties += 1;
+                }
+            }
+        }
+    }
+
+    standings.sort_by_key(|s| Reverse((s.points(), s.score)));
+    Leaderboard { rounds, standings }
+}
+
+impl Leaderboard {
+    /// One JSON object per player, best first.
+    pub fn json_lines(&self) -> Vec<Value> {
+        self.standings
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                json!({
+                    "rank": i + 1,
+                    "player": s.name,
+                    "wins": s.wins,
+                    "ties": s.ties,
+                    "losses": s.losses,
+                    "points": s.points(),
+                    "score": s.score,
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>4}  {:<20} {:>4} {:>4} {:>4} {:>6} {:>9}",
+            "Rank", "Player", "W", "T", "L", "Points", "Score"
+        )?;
+        for (i, s) in self.standings.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:<20} {:>4} {:>4} {:>4} {:>6} {:>9}",
+                i + 1,
+                s.name,
+                s.wins,
+                s.ties,
+                s.losses,
+                s.points(),
+                s.score
+            )?;
+        }
+        write!(f, "{} rounds per match", self.rounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PlayChoice::*;
+
+    #[test]
+    fn plays_matches() {
+        assert_eq!(play_match(&mut Fixed(Paper), &mut Fixed(Rock), 3), (24, 3));
+
+        // Learns that the opponent only plays rock after the first round.
+        let (counter, _) = play_match(&mut FrequencyCounter::default(), &mut Fixed(Rock), 3);
+        assert_eq!(counter, 24);
+
+        let mut shift = WinStayLoseShift::default();
+        let mut guide = GuideReplay::new("guide", vec![Paper, Scissors]).unwrap();
+        assert_eq!(
+            play_match(&mut shift, &mut guide, 4),
+            (1 + 6 + 1 + 6, 8 + 6 + 8 + 6)
+        );
+        assert!(GuideReplay::new("empty", vec![]).is_none());
+
+        let mut random = Random::new(7);
+        let first: Vec<PlayChoice> = (0..10).map(|i| random.play(i)).collect();
+        random.reset();
+        let again: Vec<PlayChoice> = (0..10).map(|i| random.play(i)).collect();
+        assert_eq!(first, again);
+    }
+
+    #[test]
+    fn ranks_players() {
+        let mut players: Vec<Box<dyn Player>> = vec![
+            Box::new(Fixed(Rock)),
+            Box::new(Fixed(Scissors)),
+            Box::new(FrequencyCounter::default()),
+        ];
+        let leaderboard = round_robin(&mut players, 10);
+
+        let names: Vec<&str> = leaderboard
+            .standings
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["frequency counter", "always rock", "always scissors"]
+        );
+        assert_eq!(leaderboard.standings[0].points(), 6);
+        assert_eq!(leaderboard.json_lines()[2]["losses"], 2);
+    }
+}
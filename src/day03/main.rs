@@ -1,91 +1,58 @@
-use nom::character::complete::{alphanumeric1, newline};
-use nom::IResult;
-use nom::multi::separated_list1;
-use nom::sequence::tuple;
-
+use advent_of_code_2022_rust::cli::{self, Args};
+use advent_of_code_2022_rust::day03::{self, Day03, Grouping};
+use advent_of_code_2022_rust::{solution, Solution};
+use serde_json::json;
+
+const LABELS: [&str; 2] = ["Total priorities", "Total priorities for 3 badges"];
+
+/// Usage: `day03 [--group-size N] [--sliding]`, plus the options of every day binary
+/// (see [`cli`]).
+///
+/// `--group-size N` also sums the badges of groups of N rucksacks instead of 3, and `--sliding`
+/// takes every run of N consecutive rucksacks as a group instead of splitting them.
 fn main() {
-    let input = include_str!("./data.txt");
-    let (res, priorities) = find_total_priorities(input).unwrap();
-    assert_eq!(res, "");
-
-    println!("Total priorities: {}", priorities);
-
-    let (res, priorities) = find_total_priorities_for_3_badges(input).unwrap();
-    assert_eq!(res, "");
-
-    println!("Total priorities for 3 badges: {}", priorities);
-}
-
-fn char_to_u32(c: &char) -> u32 {
-    if *c >= 'A' && *c <= 'Z' {
-        return *c as u32 - 'A' as u32 + 27;
-    }
-
-    *c as u32 - 'a' as u32 + 1
-}
-
-fn parse_line(input: &str) -> IResult<&str, (&str, &str)> {
-    let (input, line) = alphanumeric1(input)?;
-    let mid = line.len() / 2;
-    let left = &line[..mid];
-    let right = &line[mid..];
-    Ok((input, (left, right)))
-}
-
-fn parse_line_score(input: &str) -> IResult<&str, u32> {
-    let (input, (l, r)) = parse_line(input)?;
-    for c in l.chars() {
-        if r.contains(c) {
-            return Ok((input, char_to_u32(&c)));
+    let mut args = Args::from_env();
+    let grouping = if args.flag("--sliding") {
+        Grouping::Sliding
+    } else {
+        Grouping::Chunked
+    };
+    let result = (|| {
+        let size = match args.value("--group-size")? {
+            Some(size) => match size.parse() {
+                Ok(size) if size > 0 => Some(size),
+                _ => return Err(format!("Invalid --group-size: {}", size)),
+            },
+            None => None,
+        };
+        let options = args.options()?;
+
+        let input = options.read_input(Day03::DAY)?;
+        let report = solution::solve::<Day03>(&input, options.part).map_err(|e| e.to_string())?;
+        cli::print_report(&report, &options, LABELS);
+
+        if size.is_some() || grouping == Grouping::Sliding {
+            let size = size.unwrap_or(3);
+            let rucksacks = Day03::parse(&input).map_err(|e| e.to_string())?;
+            let total = day03::total_badge_priorities(&rucksacks, size, grouping)
+                .map_err(|e| e.to_string())?;
+
+            let sliding = grouping == Grouping::Sliding;
+            if options.json {
+                println!(
+                    "{}",
+                    json!({"group_size": size, "sliding": sliding, "answer": total})
+                );
+            } else {
+                let groups = if sliding { "sliding groups" } else { "groups" };
+                println!(
+                    "Total priorities for badges of {} of {}: {}",
+                    groups, size, total
+                );
+            }
         }
-    }
-    Ok((input, 0))
-}
-
-fn find_total_priorities(input: &str) -> IResult<&str, u32> {
-    let (input, priorities) = separated_list1(newline, parse_line_score)(input)?;
-    Ok((input, priorities.iter().sum()))
-}
+        Ok(())
+    })();
 
-fn parse_3_badges(input: &str) -> IResult<&str, (&str, &str, &str)> {
-    let (input, (badge1, _)) = tuple((alphanumeric1, newline))(input)?;
-    let (input, (badge2, _)) = tuple((alphanumeric1, newline))(input)?;
-    let (input, badge3) = alphanumeric1(input)?;
-    Ok((input, (badge1, badge2, badge3)))
-}
-
-fn parse_3_elf_badge(input: &str) -> IResult<&str, u32> {
-    let (input, (badge1, badge2, badge3)) = parse_3_badges(input)?;
-    for c in badge1.chars() {
-        if badge2.contains(c) && badge3.contains(c) {
-            return Ok((input, char_to_u32(&c)));
-        }
-    }
-    Ok((input, 0))
+    cli::exit_on_error(result);
 }
-
-fn find_total_priorities_for_3_badges(input: &str) -> IResult<&str, u32> {
-    let (input, priorities) = separated_list1(newline, parse_3_elf_badge)(input)?;
-    Ok((input, priorities.iter().sum()))
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn total_priorities() {
-        let input = include_str!("./sample.txt");
-        let (res, priorities) = find_total_priorities(input).unwrap();
-        assert_eq!(res, "");
-        assert_eq!(priorities, 157)
-    }
-
-    #[test]
-    fn total_priorities_for_3_badges() {
-        let input = include_str!("./sample.txt");
-        let (res, priorities) = find_total_priorities_for_3_badges(input).unwrap();
-        assert_eq!(res, "");
-        assert_eq!(priorities, 70)
-    }
-}
\ No newline at end of file
@@ -0,0 +1,161 @@
+//! Day 3: Rucksack Reorganization.
+
+use nom::character::complete::newline;
+use nom::IResult;
+use nom::multi::separated_list1;
+
+use crate::error::parse_all;
+use crate::{Answer, Error, Result, Solution};
+
+mod rucksack;
+
+pub use rucksack::{badge, ItemSet, Rucksack};
+
+/// Sums the priorities of misplaced items and of group badges.
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Rucksack>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, parse_rucksacks)
+    }
+
+    fn part1(rucksacks: &Self::Input) -> Result<Answer> {
+        Ok(rucksacks.iter().map(Rucksack::priority).sum::<u32>().into())
+    }
+
+    fn part2(rucksacks: &Self::Input) -> Result<Answer> {
+        Ok(total_badge_priorities(rucksacks, 3, Grouping::Chunked)?.into())
+    }
+}
+
+/// How rucksacks are put into groups to find their badges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Grouping {
+    /// Consecutive groups that do not overlap, as in the puzzle.
+    #[default]
+    Chunked,
+    /// Every run of consecutive rucksacks: the first N, then the 2nd to the N+1th, and so on.
+    Sliding,
+}
+
+/// The priority of an item: `a`-`z` are 1-26, `A`-`Z` are 27-52.
+pub fn char_to_u32(c: &char) -> u32 {
+    if *c >= 'A' && *c <= 'Z' {
+        return *c as u32 - 'A' as u32 + 27;
+    }
+
+    *c as u32 - 'a' as u32 + 1
+}
+
+fn parse_line_score(input: &str) -> IResult<&str, u32> {
+    let (input, rucksack) = Rucksack::parse(input)?;
+    Ok((input, rucksack.priority()))
+}
+
+/// Sums the priorities of the item found in both compartments of every rucksack.
+pub fn find_total_priorities(input: &str) -> IResult<&str, u32> {
+    let (input, priorities) = separated_list1(newline, parse_line_score)(input)?;
+    Ok((input, priorities.iter().sum()))
+}
+
+/// Parses one rucksack per line.
+pub fn parse_rucksacks(input: &str) -> IResult<&str, Vec<Rucksack>> {
+    separated_list1(newline, Rucksack::parse)(input)
+}
+
+/// Splits the rucksacks into groups of `size`.
+///
+/// Fails if `size` is 0, if there are fewer than `size` rucksacks, or if chunked groups would
+/// leave rucksacks out.
+pub fn groups(rucksacks: &[Rucksack], size: usize, grouping: Grouping) -> Result<Vec<&[Rucksack]>> {
+    if size == 0 {
+        return Err(Error::InvalidGroups(
+            "groups must have at least 1 rucksack".to_string(),
+        ));
+    }
+    if rucksacks.len() < size {
+        return Err(Error::InvalidGroups(format!(
+            "fewer than {} rucksacks",
+            size
+        )));
+    }
+
+    match grouping {
+        Grouping::Chunked if !rucksacks.len().is_multiple_of(size) => {
+            Err(Error::InvalidGroups(format!(
+                "{} rucksacks cannot be split into groups of {}",
+                rucksacks.len(),
+                size
+            )))
+        }
+        Grouping::Chunked => Ok(rucksacks.chunks(size).collect()),
+        Grouping::Sliding => Ok(rucksacks.windows(size).collect()),
+    }
+}
+
+/// Sums the priorities of the badge shared by every group of `size` rucksacks, see [`groups`].
+pub fn total_badge_priorities(
+    rucksacks: &[Rucksack],
+    size: usize,
+    grouping: Grouping,
+) -> Result<u32> {
+    let groups = groups(rucksacks, size, grouping)?;
+    Ok(groups
+        .into_iter()
+        .map(|group| badge(group).priority())
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_priorities() {
+        let input = include_str!("./sample.txt");
+        let (res, priorities) = find_total_priorities(input).unwrap();
+        assert_eq!(res, "");
+        assert_eq!(priorities, 157)
+    }
+
+    #[test]
+    fn total_priorities_for_3_badges() {
+        let input = include_str!("./sample.txt");
+        let (res, rucksacks) = parse_rucksacks(input).unwrap();
+        assert_eq!(res, "");
+
+        let priorities = total_badge_priorities(&rucksacks, 3, Grouping::Chunked).unwrap();
+        assert_eq!(priorities, 70)
+    }
+
+    #[test]
+    fn groups_of_any_size() {
+        let input = include_str!("./sample.txt");
+        let rucksacks = Day03::parse(input).unwrap();
+
+        assert_eq!(groups(&rucksacks, 2, Grouping::Chunked).unwrap().len(), 3);
+        assert_eq!(groups(&rucksacks, 2, Grouping::Sliding).unwrap().len(), 5);
+        assert_eq!(
+            total_badge_priorities(&rucksacks, 6, Grouping::Chunked),
+            Ok(0)
+        );
+        assert_eq!(
+            total_badge_priorities(&rucksacks, 1, Grouping::Chunked),
+            Ok(rucksacks.iter().map(|r| r.items().priority()).sum())
+        );
+
+        assert_eq!(
+            groups(&rucksacks, 4, Grouping::Chunked),
+            Err(Error::InvalidGroups(
+                "6 rucksacks cannot be split into groups of 4".to_string()
+            ))
+        );
+        assert!(groups(&rucksacks, 4, Grouping::Sliding).is_ok());
+        assert!(groups(&rucksacks, 7, Grouping::Sliding).is_err());
+        assert!(groups(&rucksacks, 0, Grouping::Sliding).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,146 @@
+//! Rucksacks as sets of items, one bit per item.
+
+use nom::character::complete::alpha1;
+use nom::combinator::map;
+use nom::IResult;
+
+use super::char_to_u32;
+
+/// A set of distinct items, where bit `p` stands for the item of priority `p` (1 to 52).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    /// The items of `s`, or `None` if it contains anything else than ASCII letters.
+    pub fn new(s: &str) -> Option<Self> {
+        let mut bits = 0;
+        for c in s.chars() {
+            if !c.is_ascii_alphabetic() {
+                return None;
+            }
+            bits |= 1 << char_to_u32(&c);
+        }
+        Some(ItemSet(bits))
+    }
+
+    /// The items in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        ItemSet(self.0 & other.0)
+    }
+
+    /// The items in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        item.is_ascii_alphabetic() && self.0 & (1 << char_to_u32(&item)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The items, by increasing priority.
+    pub fn items(&self) -> impl Iterator<Item = char> {
+        let bits = self.0;
+        (1..=52).filter(move |p| bits & (1 << p) != 0).map(item)
+    }
+
+    /// The sum of the priorities of the items, which is the priority of the item of a
+    /// single-item set.
+    pub fn priority(&self) -> u32 {
+        (1..=52).filter(|p| self.0 & (1 << p) != 0).sum()
+    }
+}
+
+fn item(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        _ => (b'A' + priority as u8 - 27) as char,
+    }
+}
+
+/// A rucksack, whose two compartments hold half of its items each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack {
+    left: ItemSet,
+    right: ItemSet,
+}
+
+impl Rucksack {
+    /// The rucksack listing `items`, or `None` if it contains anything else than ASCII letters.
+    pub fn new(items: &str) -> Option<Self> {
+        let (left, right) = items.split_at(items.len() / 2);
+        Some(Rucksack {
+            left: ItemSet::new(left)?,
+            right: ItemSet::new(right)?,
+        })
+    }
+
+    /// Parses the items of a rucksack.
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        // `alpha1` only takes ASCII letters, which `new` always accepts.
+        map(alpha1, |items| Rucksack::new(items).unwrap())(input)
+    }
+
+    /// Every item in the rucksack.
+    pub fn items(&self) -> ItemSet {
+        self.left.union(&self.right)
+    }
+
+    /// The items found in both compartments.
+    pub fn intersection(&self) -> ItemSet {
+        self.left.intersection(&self.right)
+    }
+
+    /// The priority of the items found in both compartments.
+    pub fn priority(&self) -> u32 {
+        self.intersection().priority()
+    }
+}
+
+/// The items carried by every rucksack of `group`; empty if `group` is.
+pub fn badge(group: &[Rucksack]) -> ItemSet {
+    let mut rucksacks = group.iter().map(Rucksack::items);
+    let first = rucksacks.next().unwrap_or_default();
+    rucksacks.fold(first, |badge, items| badge.intersection(&items))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersects_items() {
+        let a = ItemSet::new("abcZ").unwrap();
+        let b = ItemSet::new("cZZy").unwrap();
+
+        let both = a.intersection(&b);
+        assert_eq!(both.items().collect::<String>(), "cZ");
+        assert_eq!(both.len(), 2);
+        assert_eq!(both.priority(), 3 + 52);
+        assert!(both.contains('Z') && !both.contains('a') && !both.contains('1'));
+        assert!(ItemSet::new("ab1").is_none());
+    }
+
+    #[test]
+    fn finds_misplaced_items_and_badges() {
+        let rucksacks: Vec<Rucksack> = include_str!("./sample.txt")
+            .lines()
+            .map(|line| Rucksack::new(line).unwrap())
+            .collect();
+
+        assert_eq!(rucksacks[0].intersection().items().collect::<String>(), "p");
+        assert_eq!(rucksacks[1].priority(), 38);
+        assert_eq!(badge(&rucksacks[..3]).items().collect::<String>(), "r");
+        assert_eq!(badge(&rucksacks[3..]).priority(), 52);
+        assert!(badge(&[]).is_empty());
+
+        assert!(Rucksack::parse("ab1").is_ok_and(|(rest, _)| rest == "1"));
+    }
+}
@@ -0,0 +1,109 @@
+//! Day 4: Camp Cleanup.
+
+use nom::bytes::complete::tag;
+use nom::character::complete;
+use nom::character::complete::newline;
+use nom::IResult;
+use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+
+use crate::error::parse_all;
+use crate::{Answer, Result, Solution};
+
+/// Counts the section assignment pairs that fully (part 1) or partially (part 2) overlap.
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<(Range, Range)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, parse_ranges)
+    }
+
+    fn part1(ranges: &Self::Input) -> Result<Answer> {
+        Ok(total_full_overlaps(ranges).into())
+    }
+
+    fn part2(ranges: &Self::Input) -> Result<Answer> {
+        Ok(total_partial_overlaps(ranges).into())
+    }
+}
+
+/// An inclusive range of section IDs, written as `start-end`.
+#[derive(Debug, PartialEq)]
+pub struct Range {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Range {
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        let (input, (start, end)) = separated_pair(complete::u32, tag("-"), complete::u32)(input)?;
+        Ok((input, Range { start, end }))
+    }
+
+    /// Parses two ranges separated by a comma.
+    pub fn parse_pair(input: &str) -> IResult<&str, (Self, Self)> {
+        separated_pair(Range::parse, tag(","), Range::parse)(input)
+    }
+
+    /// Whether either range contains the other.
+    pub fn fully_overlaps(&self, other: &Self) -> bool {
+        self.fully_contains(other) || other.fully_contains(self)
+    }
+
+    pub fn fully_contains(&self, other: &Self) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    /// Whether the ranges share at least one section.
+    pub fn partial_overlaps(&self, other: &Self) -> bool {
+        self.partial_contains(other) || other.partial_contains(self)
+    }
+
+    pub fn partial_contains(&self, other: &Self) -> bool {
+        self.start <= other.start && self.end >= other.start
+    }
+}
+
+/// Parses one pair of ranges per line.
+pub fn parse_ranges(input: &str) -> IResult<&str, Vec<(Range, Range)>> {
+    separated_list1(newline, Range::parse_pair)(input)
+}
+
+/// Counts the pairs where one range contains the other.
+pub fn total_full_overlaps(ranges: &[(Range, Range)]) -> u32 {
+    ranges
+        .iter()
+        .filter(|(a, b)| a.fully_overlaps(b))
+        .count() as u32
+}
+
+/// Counts the pairs that share at least one section.
+pub fn total_partial_overlaps(ranges: &[(Range, Range)]) -> u32 {
+    ranges
+        .iter()
+        .filter(|(a, b)| a.partial_overlaps(b))
+        .count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_overlaps_count() {
+        let input = include_str!("./sample.txt");
+        let (_, ranges) = parse_ranges(input).unwrap();
+        assert_eq!(total_full_overlaps(&ranges), 2);
+    }
+
+    #[test]
+    fn partial_overlaps_count() {
+        let input = include_str!("./sample.txt");
+        let (_, ranges) = parse_ranges(input).unwrap();
+        assert_eq!(total_partial_overlaps(&ranges), 4);
+    }
+}
\ No newline at end of file
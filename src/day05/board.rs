@@ -4,14 +4,16 @@ use nom::character::complete::{anychar, newline};
 use nom::IResult;
 use nom::multi::separated_list1;
 use nom::sequence::tuple;
-use crate::command::Command;
+use super::command::Command;
+use crate::{Error, Result};
 
-#[derive(Debug, PartialEq)]
+/// The stacks of crates, bottom first.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Board {
     tiles: Vec<Vec<Tile>>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 struct Tile {
     pub val: char,
 }
@@ -23,6 +25,7 @@ impl Tile {
 }
 
 impl Board {
+    /// Parses the drawing of the stacks, including the line of stack numbers.
     pub fn parse(input: &str) -> IResult<&str, Self> {
         let mut parse_rows = separated_list1(newline, parse_row);
         let (input, mut rows) = parse_rows(input)?;
@@ -36,7 +39,7 @@ impl Board {
         for i in 0..cnt {
             let mut tile: Vec<Tile> = vec![];
             for row in rows.iter() {
-                if let Some(el) = &row[i] {
+                if let Some(Some(el)) = row.get(i) {
                     tile.push(Tile::new(el.val));
                 }
             }
@@ -47,21 +50,46 @@ impl Board {
         Ok((input, Self { tiles }))
     }
 
-    pub fn execute(&mut self, command: &Command) {
+    /// Moves crates one at a time, reversing their order.
+    pub fn execute(&mut self, command: &Command) -> Result<()> {
+        self.check_stacks(command)?;
+
         for _ in 0..command.size {
-            let tile = self.tiles[command.from].pop().unwrap();
+            let tile = self.tiles[command.from]
+                .pop()
+                .ok_or(Error::EmptyStack { stack: command.from + 1 })?;
             self.tiles[command.to].push(tile);
         }
+
+        Ok(())
     }
 
-    pub fn execute_v2(&mut self, command: &Command) {
+    /// Moves crates all at once, keeping their order.
+    pub fn execute_v2(&mut self, command: &Command) -> Result<()> {
+        self.check_stacks(command)?;
+
         let len = self.tiles[command.from].len();
-        let from_range = len - command.size;
+        let from_range = len
+            .checked_sub(command.size)
+            .ok_or(Error::EmptyStack { stack: command.from + 1 })?;
 
         let moved: Vec<_> = self.tiles[command.from].splice(from_range..len, vec![]).collect();
         self.tiles[command.to].extend(moved);
+
+        Ok(())
+    }
+
+    fn check_stacks(&self, command: &Command) -> Result<()> {
+        for stack in [command.from, command.to] {
+            if stack >= self.tiles.len() {
+                return Err(Error::UnknownStack { stack: stack + 1 });
+            }
+        }
+
+        Ok(())
     }
 
+    /// The crate on top of each non-empty stack.
     pub fn first_row(&self) -> String {
         let mut result = String::new();
         for tile in self.tiles.iter() {
@@ -125,7 +153,7 @@ mod tests {
             to: 1,
         };
 
-        board.execute(&command);
+        board.execute(&command).unwrap();
 
         assert_eq!(board.tiles, vec![
             vec![Tile::new('a')],
@@ -150,7 +178,7 @@ mod tests {
             to: 1,
         };
 
-        board.execute_v2(&command);
+        board.execute_v2(&command).unwrap();
 
         assert_eq!(board.tiles, vec![
             vec![Tile::new('a')],
@@ -158,4 +186,23 @@ mod tests {
             vec![],
         ]);
     }
+
+    #[test]
+    fn test_command_on_empty_stack() {
+        let mut board = Board {
+            tiles: vec![
+                vec![Tile::new('a')],
+                vec![],
+            ]
+        };
+
+        let command = Command {
+            size: 2,
+            from: 0,
+            to: 1,
+        };
+
+        assert_eq!(board.clone().execute(&command), Err(Error::EmptyStack { stack: 1 }));
+        assert_eq!(board.execute_v2(&command), Err(Error::EmptyStack { stack: 1 }));
+    }
 }
\ No newline at end of file
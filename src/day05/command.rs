@@ -2,9 +2,11 @@ use nom::bytes::complete::tag;
 use nom::character::complete;
 use nom::character::complete::newline;
 use nom::multi::separated_list1;
+use nom::combinator::verify;
 use nom::sequence::tuple;
 use nom::IResult;
 
+/// A `move <size> from <from> to <to>` step, with zero-based stack indices.
 #[derive(Debug, PartialEq)]
 pub struct Command {
     pub size: usize,
@@ -18,9 +20,9 @@ impl Command {
             tag("move "),
             complete::u32,
             tag(" from "),
-            complete::u32,
+            verify(complete::u32, |n| *n > 0),
             tag(" to "),
-            complete::u32,
+            verify(complete::u32, |n| *n > 0),
         ))(input)?;
         Ok((
             input,
@@ -32,6 +34,7 @@ impl Command {
         ))
     }
 
+    /// Parses one command per line.
     pub fn parse_many(input: &str) -> IResult<&str, Vec<Self>> {
         separated_list1(newline, Self::parse)(input)
     }
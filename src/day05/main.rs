@@ -1,50 +1,6 @@
-use nom::bytes::complete::tag;
-use nom::IResult;
-use nom::sequence::separated_pair;
-
-mod command;
-mod board;
-
-use board::Board;
-use command::Command;
+use advent_of_code_2022_rust::cli;
+use advent_of_code_2022_rust::day05::Day05;
 
 fn main() {
-    let input = include_str!("./data.txt");
-    let (_, (mut board, commands)) = parse_board_and_commands(input).unwrap();
-
-    for command in commands {
-        board.execute(&command);
-    }
-
-    println!("First row: {}", board.first_row());
-
-    let input = include_str!("./data.txt");
-    let (_, (mut board, commands)) = parse_board_and_commands(input).unwrap();
-
-    for command in commands {
-        board.execute_v2(&command);
-    }
-
-    println!("First row: {}", board.first_row());
+    cli::run::<Day05>(["First row", "First row"]);
 }
-
-fn parse_board_and_commands(input: &str) -> IResult<&str, (Board, Vec<Command>)> {
-    separated_pair(Board::parse, tag("\n\n"), Command::parse_many)(input)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn top_crate() {
-        let input = include_str!("./sample.txt");
-        let (_, (mut board, commands)) = parse_board_and_commands(input).unwrap();
-
-        for command in commands {
-            board.execute(&command);
-        }
-
-        assert_eq!("CMZ", board.first_row());
-    }
-}
\ No newline at end of file
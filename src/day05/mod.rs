@@ -0,0 +1,69 @@
+//! Day 5: Supply Stacks.
+
+use nom::bytes::complete::tag;
+use nom::IResult;
+use nom::sequence::separated_pair;
+
+mod command;
+mod board;
+
+pub use board::Board;
+pub use command::Command;
+
+use crate::error::parse_all;
+use crate::{Answer, Result, Solution};
+
+/// Rearranges the crates one at a time (part 1) or several at once (part 2).
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = (Board, Vec<Command>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, parse_board_and_commands)
+    }
+
+    fn part1((board, commands): &Self::Input) -> Result<Answer> {
+        let mut board = board.clone();
+
+        for command in commands {
+            board.execute(command)?;
+        }
+
+        Ok(board.first_row().into())
+    }
+
+    fn part2((board, commands): &Self::Input) -> Result<Answer> {
+        let mut board = board.clone();
+
+        for command in commands {
+            board.execute_v2(command)?;
+        }
+
+        Ok(board.first_row().into())
+    }
+}
+
+/// Parses the starting stacks and the rearrangement procedure, separated by a blank line.
+pub fn parse_board_and_commands(input: &str) -> IResult<&str, (Board, Vec<Command>)> {
+    separated_pair(Board::parse, tag("\n\n"), Command::parse_many)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_crate() {
+        let input = include_str!("./sample.txt");
+        let (_, (mut board, commands)) = parse_board_and_commands(input).unwrap();
+
+        for command in commands {
+            board.execute(&command).unwrap();
+        }
+
+        assert_eq!("CMZ", board.first_row());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,87 @@
+//! Day 6: Tuning Trouble.
+
+use nom::character::complete::alpha1;
+
+use crate::error::parse_all;
+use crate::{Answer, Error, Result, Solution};
+
+/// Finds the start-of-packet (part 1) and start-of-message (part 2) markers.
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, alpha1).map(str::to_string)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        let marker = parse_marker(input)
+            .ok_or_else(|| Error::NoSolution("no start-of-packet marker found".to_string()))?;
+        Ok(marker.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let message = parse_message(input)
+            .ok_or_else(|| Error::NoSolution("no start-of-message marker found".to_string()))?;
+        Ok(message.into())
+    }
+}
+
+/// The number of characters read before the first 4 distinct characters.
+pub fn parse_marker(input: &str) -> Option<usize> {
+    parse_marker_for_n_chars(input, 4)
+}
+
+/// The number of characters read before the first 14 distinct characters.
+pub fn parse_message(input: &str) -> Option<usize> {
+    parse_marker_for_n_chars(input, 14)
+}
+
+/// The number of characters read before the first `n` distinct characters, if any.
+pub fn parse_marker_for_n_chars (input: &str, n: usize) -> Option<usize> {
+    if input.len() < n {
+        return None;
+    }
+
+    for i in n-1..input.len() {
+        let start = i+1-n;
+        if substring_contains_duplicates(&input[start..i+1]) {
+            return Some(i+1);
+        }
+    }
+
+    None
+}
+
+fn substring_contains_duplicates(input: &str) -> bool {
+    let mut chars = input.chars().collect::<Vec<char>>();
+    chars.sort();
+    chars.dedup();
+    chars.len() == input.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_marker() {
+        assert_eq!(parse_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Some(7));
+        assert_eq!(parse_marker("bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(5));
+        assert_eq!(parse_marker("nppdvjthqldpwncqszvftbrmjlhg"), Some(6));
+        assert_eq!(parse_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(10));
+        assert_eq!(parse_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(11));
+    }
+
+    #[test]
+    fn test_parse_message() {
+        assert_eq!(parse_message("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Some(19));
+        assert_eq!(parse_message("bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(23));
+        assert_eq!(parse_message("nppdvjthqldpwncqszvftbrmjlhg"), Some(23));
+        assert_eq!(parse_message("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(29));
+        assert_eq!(parse_message("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(26));
+    }
+}
\ No newline at end of file
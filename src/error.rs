@@ -0,0 +1,204 @@
+use std::fmt;
+
+use nom::error::ErrorKind;
+use nom::IResult;
+
+/// Everything that can go wrong while solving a puzzle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The input does not match the expected format.
+    Parse {
+        line: usize,
+        column: usize,
+        snippet: String,
+    },
+    /// The input was parsed up to this point but has more content left.
+    TrailingInput {
+        line: usize,
+        column: usize,
+        snippet: String,
+    },
+    /// Day 2: a rule table is inconsistent.
+    InvalidRules(String),
+    /// Day 3: the rucksacks cannot be split into groups of the requested size.
+    InvalidGroups(String),
+    /// Day 5: a crate was moved from a stack without enough crates.
+    EmptyStack { stack: usize },
+    /// Day 5: a command refers to a stack that does not exist.
+    UnknownStack { stack: usize },
+    /// The input is well-formed but has no answer.
+    NoSolution(String),
+    /// The input could not be read.
+    Io(String),
+    /// A number or a sum does not fit in its type.
+    Overflow(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                snippet,
+            } => write!(
+                f,
+                "parse error at line {}, column {}: unexpected `{}`",
+                line,
+                column,
+                snippet.escape_debug()
+            ),
+            Error::TrailingInput {
+                line,
+                column,
+                snippet,
+            } => write!(
+                f,
+                "unparsed input at line {}, column {}: `{}`",
+                line,
+                column,
+                snippet.escape_debug()
+            ),
+            Error::InvalidRules(reason) => write!(f, "invalid rules: {}", reason),
+            Error::InvalidGroups(reason) => write!(f, "invalid groups: {}", reason),
+            Error::EmptyStack { stack } => {
+                write!(f, "cannot take a crate from empty stack {}", stack)
+            }
+            Error::UnknownStack { stack } => write!(f, "stack {} does not exist", stack),
+            Error::NoSolution(reason) => write!(f, "no solution: {}", reason),
+            Error::Io(reason) => write!(f, "cannot read input: {}", reason),
+            Error::Overflow(what) => write!(f, "overflow: {} is too large", what),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+const SNIPPET_LEN: usize = 20;
+
+/// Runs `parser` over the whole input, allowing only a final newline to be left over.
+///
+/// Parsers fail with [`ErrorKind::TooLarge`] to report an [`Error::Overflow`].
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<T> {
+    match parser(input) {
+        Ok((rest, value)) if rest.is_empty() || rest == "\n" => Ok(value),
+        Ok((rest, _)) => {
+            // Point at the offending text rather than the separator in front of it.
+            let (line, column, snippet) = locate(input, rest.trim_start_matches('\n'));
+            Err(Error::TrailingInput {
+                line,
+                column,
+                snippet,
+            })
+        }
+        Err(nom::Err::Failure(e)) if e.code == ErrorKind::TooLarge => {
+            let (line, column, _) = locate(input, e.input);
+            Err(Error::Overflow(format!(
+                "the value at line {}, column {}",
+                line, column
+            )))
+        }
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+            let (line, column, snippet) = locate(input, e.input);
+            Err(Error::Parse {
+                line,
+                column,
+                snippet,
+            })
+        }
+        Err(nom::Err::Incomplete(_)) => {
+            let (line, column, snippet) = locate(input, &input[input.len()..]);
+            Err(Error::Parse {
+                line,
+                column,
+                snippet,
+            })
+        }
+    }
+}
+
+/// A parse error pointing at `at`, which must be a slice of `input`, for values that are
+/// parsed first and checked afterwards.
+pub fn parse_error_at(input: &str, at: &str) -> Error {
+    let offset = at.as_ptr() as usize - input.as_ptr() as usize;
+    let (line, column, snippet) = locate(input, &input[offset..]);
+    Error::Parse {
+        line,
+        column,
+        snippet,
+    }
+}
+
+fn locate(input: &str, rest: &str) -> (usize, usize, String) {
+    let consumed = &input[..input.len() - rest.len()];
+    let line = consumed.matches('\n').count() + 1;
+    let column = match consumed.rfind('\n') {
+        Some(i) => consumed[i + 1..].chars().count() + 1,
+        None => consumed.chars().count() + 1,
+    };
+
+    // Up to the end of the line, keeping a `\r` that may be what failed to parse.
+    let snippet = rest.split('\n').next().unwrap_or("");
+    let snippet = match snippet.char_indices().nth(SNIPPET_LEN) {
+        Some((i, _)) => format!("{}...", &snippet[..i]),
+        None => snippet.to_string(),
+    };
+
+    (line, column, snippet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::character::complete::{alpha1, newline};
+    use nom::multi::separated_list1;
+
+    #[test]
+    fn reports_trailing_input_position() {
+        let res = parse_all("abc\ndef\n12 and more", |s| {
+            separated_list1(newline, alpha1)(s)
+        });
+        assert_eq!(
+            res,
+            Err(Error::TrailingInput {
+                line: 3,
+                column: 1,
+                snippet: "12 and more".to_string()
+            })
+        );
+
+        let res = parse_all("abc\nde1f", |s| separated_list1(newline, alpha1)(s));
+        assert_eq!(
+            res,
+            Err(Error::TrailingInput {
+                line: 2,
+                column: 3,
+                snippet: "1f".to_string()
+            })
+        );
+
+        let res = parse_all("abc\r\ndef", |s| separated_list1(newline, alpha1)(s));
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "unparsed input at line 1, column 4: `\\r`"
+        );
+    }
+
+    #[test]
+    fn reports_parse_error_position() {
+        let res = parse_all("12\n", alpha1);
+        assert_eq!(
+            res,
+            Err(Error::Parse {
+                line: 1,
+                column: 1,
+                snippet: "12".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Locating puzzle inputs at runtime.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+pub const INPUTS_DIR_ENV: &str = "AOC_INPUTS_DIR";
+pub const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+/// The inputs directory: `$AOC_INPUTS_DIR`, or `./inputs`.
+pub fn inputs_dir() -> PathBuf {
+    env::var_os(INPUTS_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUTS_DIR))
+}
+
+/// The path of a day's input inside the inputs directory, e.g. `inputs/day01.txt`.
+pub fn input_path(inputs_dir: &Path, day: u8) -> PathBuf {
+    inputs_dir.join(format!("day{:02}.txt", day))
+}
+
+/// Reads a day's input.
+///
+/// `path` is either a file or `-` for stdin; without it the inputs directory is
+/// searched and the embedded input, if any, is used as the last resort.
+pub fn read(
+    day: u8,
+    path: Option<&str>,
+    inputs_dir: &Path,
+    embedded: Option<&str>,
+) -> io::Result<String> {
+    match path {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => read_file(Path::new(path)),
+        None => {
+            let path = input_path(inputs_dir, day);
+            match (path.exists(), embedded) {
+                (false, Some(embedded)) => Ok(embedded.to_string()),
+                _ => read_file(&path),
+            }
+        }
+    }
+}
+
+/// Opens a day's input for streaming, looking for it like [`read`] does.
+pub fn open<'a>(
+    day: u8,
+    path: Option<&str>,
+    inputs_dir: &Path,
+    embedded: Option<&'a str>,
+) -> io::Result<Box<dyn BufRead + 'a>> {
+    match path {
+        Some("-") => Ok(Box::new(io::stdin().lock())),
+        Some(path) => open_file(Path::new(path)),
+        None => {
+            let path = input_path(inputs_dir, day);
+            match (path.exists(), embedded) {
+                (false, Some(embedded)) => Ok(Box::new(embedded.as_bytes())),
+                _ => open_file(&path),
+            }
+        }
+    }
+}
+
+fn open_file<'a>(path: &Path) -> io::Result<Box<dyn BufRead + 'a>> {
+    let file = File::open(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+fn read_file(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_to_embedded_input() {
+        let dir = Path::new("does-not-exist");
+        let input = read(1, None, dir, Some("1000")).unwrap();
+        assert_eq!(input, "1000");
+
+        assert!(read(1, None, dir, None).is_err());
+    }
+
+    #[test]
+    fn prefers_inputs_dir_over_embedded() {
+        let dir = env::temp_dir().join("aoc-input-test");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(input_path(&dir, 1), "2000").unwrap();
+
+        let input = read(1, None, &dir, Some("1000")).unwrap();
+        assert_eq!(input, "2000");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+//! Solutions to [Advent of Code 2022](https://adventofcode.com/2022).
+//!
+//! Every day implements [`Solution`] and is registered in [`DAYS`] so it can be run by the
+//! `aoc` binary; the `dayNN` binaries are thin wrappers around the same modules.
+
+use std::io;
+use std::path::Path;
+
+pub mod answers;
+pub mod cli;
+pub mod client;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod error;
+pub mod input;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+
+pub use error::{Error, Result};
+pub use solution::{Answer, Report, Solution};
+
+type Solver = fn(&str, Option<u8>) -> Result<Report>;
+
+/// A registered day, with its solution erased behind a function pointer.
+pub struct Day {
+    pub number: u8,
+    /// The input bundled at compile time, used when no other input is found.
+    pub embedded: Option<&'static str>,
+    solve: Solver,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(embedded: Option<&'static str>) -> Self {
+        Day {
+            number: S::DAY,
+            embedded,
+            solve: solution::solve::<S>,
+        }
+    }
+
+    /// Solves the given part, or both parts when `part` is `None`.
+    pub fn solve(&self, input: &str, part: Option<u8>) -> Result<Report> {
+        (self.solve)(input, part)
+    }
+
+    /// Reads this day's input, see [`input::read`].
+    pub fn read_input(&self, path: Option<&str>, inputs_dir: &Path) -> io::Result<String> {
+        input::read(self.number, path, inputs_dir, self.embedded)
+    }
+}
+
+#[cfg(feature = "embedded-inputs")]
+macro_rules! embedded {
+    ($path:literal) => {
+        Some(include_str!($path))
+    };
+}
+
+#[cfg(not(feature = "embedded-inputs"))]
+macro_rules! embedded {
+    ($path:literal) => {
+        None
+    };
+}
+
+/// Every implemented day, in order.
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(embedded!("./day01/data.txt")),
+    Day::new::<day02::Day02>(embedded!("./day02/data.txt")),
+    Day::new::<day03::Day03>(embedded!("./day03/data.txt")),
+    Day::new::<day04::Day04>(embedded!("./day04/data.txt")),
+    Day::new::<day05::Day05>(embedded!("./day05/data.txt")),
+    Day::new::<day06::Day06>(embedded!("./day06/data.txt")),
+];
+
+/// Looks up a day by its number.
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
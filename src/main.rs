@@ -1,3 +1,386 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022_rust::answers::{self, Answers, Verdict};
+use advent_of_code_2022_rust::client::{Client, Fetched};
+use advent_of_code_2022_rust::submit::{self, Hint, Outcome};
+use advent_of_code_2022_rust::{cli, find_day, input, scaffold, Day, Report, DAYS};
+
+const USAGE: &str = "Usage:
+    aoc run <day> [--part 1|2] [--input <file|->] [--inputs <dir>] [--answers <file>] [--json]
+    aoc run --all [--inputs <dir>] [--answers <file>]    (days run in parallel)
+    aoc list
+    aoc fetch <day>... [--inputs <dir>]
+    aoc submit <day> <part> [--input <file|->] [--inputs <dir>]
+    aoc new-day <day> [--root <dir>]
+
+Inputs are read from <dir>/dayNN.txt (default: $AOC_INPUTS_DIR or ./inputs),
+falling back to the input embedded in the binary.
+
+Inputs are fetched with the session token from $AOC_SESSION or .aoc-session,
+from $AOC_BASE_URL (default: https://adventofcode.com), and cached in <dir>.
+Submissions are logged in <dir>/submissions.toml to refuse known-wrong answers.
+
+Answers are checked against <file> (default: $AOC_ANSWERS or ./answers.toml)
+when it exists.
+
+new-day generates src/dayNN in the crate at <dir> (default: .) and registers it
+in Cargo.toml and src/lib.rs.";
+
 fn main() {
-    println!("Hello, world!");
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("list") => {
+            list();
+            Ok(())
+        }
+        Some("fetch") => fetch(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("new-day") => new_day(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let mut day = None;
+    let mut part = None;
+    let mut all = false;
+    let mut json = false;
+    let mut path = None;
+    let mut inputs_dir = input::inputs_dir();
+    let mut answers_path = answers::answers_path();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--json" => json = true,
+            "--part" => {
+                let value = args.next().ok_or("--part requires a value")?;
+                part = Some(parse_part(value)?);
+            }
+            "--input" => path = Some(args.next().ok_or("--input requires a value")?),
+            "--inputs" => {
+                inputs_dir = PathBuf::from(args.next().ok_or("--inputs requires a value")?)
+            }
+            "--answers" => {
+                answers_path = PathBuf::from(args.next().ok_or("--answers requires a value")?)
+            }
+            value => day = Some(parse_day(value)?),
+        }
+    }
+
+    if all && (day.is_some() || part.is_some() || path.is_some() || json) {
+        return Err("--all does not take a day, --part, --input or --json".to_string());
+    }
+
+    let answers = Answers::load_or_default(&answers_path)?;
+
+    if all {
+        return run_all(&inputs_dir, &answers);
+    }
+
+    let day = day.ok_or(USAGE)?;
+    let day = find_day(day).ok_or(format!("Day {} is not implemented", day))?;
+    let input = read_input(day, path.map(String::as_str), &inputs_dir)?;
+
+    let report = day
+        .solve(&input, part)
+        .map_err(|e| format!("Day {:02}: {}", day.number, e))?;
+
+    if json {
+        for line in cli::json_lines(&report) {
+            println!("{}", line);
+        }
+    } else {
+        for part in &report.parts {
+            println!(
+                "Day {:02} part {}: {} (in {:.2?}){}",
+                day.number,
+                part.part,
+                part.answer,
+                part.elapsed,
+                verdict_suffix(&answers.verify(day.number, part.part, &part.answer))
+            );
+        }
+    }
+
+    check_mismatches(&answers, &[report])
+}
+
+/// Solves every day on its own thread, then prints the results in day order.
+fn run_all(inputs_dir: &Path, answers: &Answers) -> Result<(), String> {
+    let start = Instant::now();
+
+    // `None` for a day without input yet, such as a freshly scaffolded one.
+    let results: Vec<Result<Option<Report>, String>> = thread::scope(|scope| {
+        let handles: Vec<_> = DAYS
+            .iter()
+            .map(|day| {
+                scope.spawn(move || {
+                    let input = read_input(day, None, inputs_dir)?;
+                    if input.trim().is_empty() {
+                        return Ok(None);
+                    }
+                    day.solve(&input, None).map(Some).map_err(|e| e.to_string())
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err("panicked".to_string()))
+            })
+            .collect()
+    });
+
+    let wall_time = start.elapsed();
+
+    println!(
+        "{:<5} {:<20} {:<20} {:>10} {:>10} {:>10}",
+        "Day", "Part 1", "Part 2", "Parse", "Time 1", "Time 2"
+    );
+
+    let mut reports = vec![];
+    let mut failed = 0;
+    let mut skipped = 0;
+    for (day, result) in DAYS.iter().zip(results) {
+        match result {
+            Ok(None) => {
+                skipped += 1;
+                println!("{:<5} skipped: no input", format!("{:02}", day.number));
+            }
+            Ok(Some(report)) => {
+                println!(
+                    "{:<5} {:<20} {:<20} {:>10.2?} {:>10} {:>10}",
+                    format!("{:02}", day.number),
+                    answer(&report, 1, answers),
+                    answer(&report, 2, answers),
+                    report.parse_time,
+                    part_time(&report, 1),
+                    part_time(&report, 2)
+                );
+                reports.push(report);
+            }
+            Err(e) => {
+                failed += 1;
+                println!("{:<5} error: {}", format!("{:02}", day.number), e);
+            }
+        }
+    }
+
+    let cpu_time: Duration = reports.iter().map(Report::total_time).sum();
+    println!(
+        "{} days, {} failed, {} skipped, total time {:.2?} ({:.2?} across threads)",
+        DAYS.len(),
+        failed,
+        skipped,
+        wall_time,
+        cpu_time
+    );
+
+    check_mismatches(answers, &reports)?;
+    if failed > 0 {
+        return Err(format!("{} days failed", failed));
+    }
+    Ok(())
+}
+
+fn part_time(report: &Report, part: u8) -> String {
+    report
+        .parts
+        .iter()
+        .find(|p| p.part == part)
+        .map(|p| format!("{:.2?}", p.elapsed))
+        .unwrap_or_default()
+}
+
+fn answer(report: &Report, part: u8, answers: &Answers) -> String {
+    match report.answer(part) {
+        Some(answer) => match answers.verify(report.day, part, answer) {
+            Verdict::Correct => format!("{} ✓", answer),
+            Verdict::Wrong { .. } => format!("{} ✗", answer),
+            Verdict::Unknown => answer.to_string(),
+        },
+        None => String::new(),
+    }
+}
+
+fn verdict_suffix(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Correct => " ✓".to_string(),
+        Verdict::Wrong { expected } => format!(" ✗ expected {}", expected),
+        Verdict::Unknown => String::new(),
+    }
+}
+
+fn check_mismatches(answers: &Answers, reports: &[Report]) -> Result<(), String> {
+    let mismatches: Vec<String> = reports
+        .iter()
+        .flat_map(|report| {
+            answers
+                .mismatches(report)
+                .into_iter()
+                .map(move |(part, actual, expected)| {
+                    format!(
+                        "Day {:02} part {}: expected {}, got {}",
+                        report.day, part, expected, actual
+                    )
+                })
+        })
+        .collect();
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches.join("\n"))
+    }
+}
+
+fn read_input(day: &Day, path: Option<&str>, inputs_dir: &Path) -> Result<String, String> {
+    day.read_input(path, inputs_dir)
+        .map_err(|e| format!("Day {:02}: {}", day.number, e))
+}
+
+fn fetch(args: &[String]) -> Result<(), String> {
+    let mut days = vec![];
+    let mut inputs_dir = input::inputs_dir();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--inputs" => {
+                inputs_dir = PathBuf::from(args.next().ok_or("--inputs requires a value")?)
+            }
+            value => days.push(parse_day(value)?),
+        }
+    }
+
+    if days.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    let client = Client::from_env();
+    for day in days {
+        let (path, fetched) = client.fetch_input(&inputs_dir, day)?;
+        match fetched {
+            Fetched::Downloaded => println!("Day {:02}: downloaded to {}", day, path.display()),
+            Fetched::Cached => println!("Day {:02}: already cached in {}", day, path.display()),
+        }
+    }
+
+    Ok(())
+}
+
+fn submit(args: &[String]) -> Result<(), String> {
+    let mut positional = vec![];
+    let mut path = None;
+    let mut inputs_dir = input::inputs_dir();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => path = Some(args.next().ok_or("--input requires a value")?),
+            "--inputs" => {
+                inputs_dir = PathBuf::from(args.next().ok_or("--inputs requires a value")?)
+            }
+            value => positional.push(value),
+        }
+    }
+
+    let (day, part) = match positional[..] {
+        [day, part] => (parse_day(day)?, parse_part(part)?),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let day = find_day(day).ok_or(format!("Day {} is not implemented", day))?;
+    let input = read_input(day, path.map(String::as_str), &inputs_dir)?;
+    let report = day
+        .solve(&input, Some(part))
+        .map_err(|e| format!("Day {:02}: {}", day.number, e))?;
+    let answer = report.answer(part).unwrap();
+
+    println!(
+        "Submitting {} for day {:02} part {}",
+        answer, day.number, part
+    );
+    let log = submit::submissions_path(&inputs_dir);
+    let outcome = submit::submit(&Client::from_env(), &log, day.number, part, answer)?;
+
+    match outcome {
+        Outcome::Correct => println!("That's the right answer!"),
+        Outcome::Wrong { hint, wait } => {
+            let hint = match hint {
+                Some(Hint::TooHigh) => " (too high)",
+                Some(Hint::TooLow) => " (too low)",
+                None => "",
+            };
+            println!("That's not the right answer{}", hint);
+            if let Some(wait) = wait {
+                println!("Wait {}s before trying again", wait.as_secs());
+            }
+        }
+        Outcome::TooRecent { wait } => {
+            println!("Answered too recently, wait {}s", wait.as_secs())
+        }
+        Outcome::AlreadySolved => println!("This part is already solved"),
+        Outcome::Unknown(text) => println!("Unexpected response: {}", text),
+    }
+
+    Ok(())
+}
+
+fn new_day(args: &[String]) -> Result<(), String> {
+    let mut day = None;
+    let mut root = PathBuf::from(".");
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--root" => root = PathBuf::from(args.next().ok_or("--root requires a value")?),
+            value => day = Some(parse_day(value)?),
+        }
+    }
+
+    let day = day.ok_or(USAGE)?;
+    if find_day(day).is_some() {
+        return Err(format!("Day {} is already implemented", day));
+    }
+
+    for path in scaffold::new_day(&root, day)? {
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn list() {
+    for day in DAYS {
+        println!("Day {:02}", day.number);
+    }
+}
+
+fn parse_day(value: &str) -> Result<u8, String> {
+    value.parse().map_err(|_| format!("Invalid day: {}", value))
+}
+
+fn parse_part(value: &str) -> Result<u8, String> {
+    match value {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("Invalid part: {}", value)),
+    }
 }
@@ -0,0 +1,207 @@
+//! Generating the skeleton of a new day and wiring it into the crate.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MODULE_TEMPLATE: &str = r#"//! Day {day}.
+
+use nom::character::complete;
+use nom::character::complete::newline;
+use nom::IResult;
+use nom::multi::separated_list1;
+
+use crate::error::parse_all;
+use crate::{Answer, Error, Result, Solution};
+
+pub struct Day{dd};
+
+impl Solution for Day{dd} {
+    const DAY: u8 = {day};
+
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_all(input, parse_lines)
+    }
+
+    fn part1(_input: &Self::Input) -> Result<Answer> {
+        Err(Error::NoSolution("part 1 is not solved yet".to_string()))
+    }
+
+    fn part2(_input: &Self::Input) -> Result<Answer> {
+        Err(Error::NoSolution("part 2 is not solved yet".to_string()))
+    }
+}
+
+/// Parses one number per line.
+pub fn parse_lines(input: &str) -> IResult<&str, Vec<u32>> {
+    separated_list1(newline, complete::u32)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "fill in sample.txt and the expected answer"]
+    fn part1() {
+        let input = include_str!("./sample.txt");
+        let input = Day{dd}::parse(input).unwrap();
+        assert_eq!(Day{dd}::part1(&input), Ok(Answer::Number(0)));
+    }
+
+    #[test]
+    #[ignore = "fill in sample.txt and the expected answer"]
+    fn part2() {
+        let input = include_str!("./sample.txt");
+        let input = Day{dd}::parse(input).unwrap();
+        assert_eq!(Day{dd}::part2(&input), Ok(Answer::Number(0)));
+    }
+}
+"#;
+
+const MAIN_TEMPLATE: &str = r#"use advent_of_code_2022_rust::cli;
+use advent_of_code_2022_rust::day{dd}::Day{dd};
+
+fn main() {
+    cli::run::<Day{dd}>(["Part 1", "Part 2"]);
+}
+"#;
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{dd}", &format!("{:02}", day))
+        .replace("{day}", &day.to_string())
+}
+
+/// Creates `src/dayNN` under `root` and registers the day in `Cargo.toml` and `src/lib.rs`,
+/// returning every file created or changed.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day: {}", day));
+    }
+
+    let dir = root.join(format!("src/day{:02}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let cargo_path = root.join("Cargo.toml");
+    let lib_path = root.join("src/lib.rs");
+    let cargo = read(&cargo_path)?;
+    let lib = read(&lib_path)?;
+
+    // Compute every change before writing anything, so a failure leaves the tree untouched.
+    let cargo = register_bin(&cargo, day);
+    let lib = register_module(&lib, day)?;
+
+    let files = [
+        (dir.join("mod.rs"), render(MODULE_TEMPLATE, day)),
+        (dir.join("main.rs"), render(MAIN_TEMPLATE, day)),
+        (dir.join("sample.txt"), String::new()),
+        (dir.join("data.txt"), String::new()),
+        (cargo_path, cargo),
+        (lib_path, lib),
+    ];
+
+    fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    for (path, content) in &files {
+        fs::write(path, content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Appends a `[[bin]]` target for the day to `Cargo.toml`.
+pub fn register_bin(cargo: &str, day: u8) -> String {
+    format!(
+        "{}\n\n[[bin]]\nname = \"day{:02}\"\npath = \"src/day{:02}/main.rs\"\n",
+        cargo.trim_end(),
+        day,
+        day
+    )
+}
+
+/// Declares the day module in `lib.rs` and adds it to `DAYS`, after the last existing day.
+pub fn register_module(lib: &str, day: u8) -> Result<String, String> {
+    let module = format!("pub mod day{:02};", day);
+    let entry = format!(
+        "    Day::new::<day{:02}::Day{:02}>(embedded!(\"./day{:02}/data.txt\")),",
+        day, day, day
+    );
+
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+
+    let last_module = lines
+        .iter()
+        .rposition(|l| l.starts_with("pub mod day"))
+        .ok_or("No `pub mod dayNN;` declaration found in lib.rs")?;
+    lines.insert(last_module + 1, module);
+
+    let last_entry = lines
+        .iter()
+        .rposition(|l| l.trim_start().starts_with("Day::new::<day"))
+        .ok_or("No `Day::new::<dayNN::DayNN>` entry found in DAYS")?;
+    lines.insert(last_entry + 1, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod day01;
+pub mod day02;
+pub mod error;
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(embedded!(\"./day01/data.txt\")),
+    Day::new::<day02::Day02>(embedded!(\"./day02/data.txt\")),
+];
+";
+
+    #[test]
+    fn registers_module_after_last_day() {
+        assert_eq!(
+            register_module(LIB, 7).unwrap(),
+            "pub mod day01;
+pub mod day02;
+pub mod day07;
+pub mod error;
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(embedded!(\"./day01/data.txt\")),
+    Day::new::<day02::Day02>(embedded!(\"./day02/data.txt\")),
+    Day::new::<day07::Day07>(embedded!(\"./day07/data.txt\")),
+];
+"
+        );
+    }
+
+    #[test]
+    fn generates_day_files() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"aoc\"\n").unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+
+        let files = new_day(&root, 7).unwrap();
+        assert_eq!(files.len(), 6);
+
+        let module = fs::read_to_string(root.join("src/day07/mod.rs")).unwrap();
+        assert!(module.contains("pub struct Day07;"));
+        assert!(module.contains("const DAY: u8 = 7;"));
+
+        let cargo = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(cargo.ends_with("[[bin]]\nname = \"day07\"\npath = \"src/day07/main.rs\"\n"));
+
+        assert!(new_day(&root, 7).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
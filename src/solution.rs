@@ -0,0 +1,139 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+
+/// The answer to a puzzle part: either a number or a string (e.g. day 5 crate labels).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => f.pad(&n.to_string()),
+            Answer::Text(s) => f.pad(s),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// A day's puzzle: the input is parsed once and shared by both parts.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    fn part2(input: &Self::Input) -> Result<Answer>;
+}
+
+/// The answers of a run, with the time spent parsing and solving each part.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+impl Report {
+    pub fn answer(&self, part: u8) -> Option<&Answer> {
+        self.parts
+            .iter()
+            .find(|p| p.part == part)
+            .map(|p| &p.answer)
+    }
+
+    /// Parse time plus the time spent on every part.
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|p| p.elapsed).sum::<Duration>()
+    }
+}
+
+/// Parses `input` and solves the given part, or both parts when `part` is `None`.
+pub fn solve<S: Solution>(input: &str, part: Option<u8>) -> Result<Report> {
+    solve_with::<S>(input, part, S::parse)
+}
+
+/// Like [`solve`], parsing `input` with `parse` instead of [`Solution::parse`].
+pub fn solve_with<S: Solution>(
+    input: &str,
+    part: Option<u8>,
+    parse: impl FnOnce(&str) -> Result<S::Input>,
+) -> Result<Report> {
+    let start = Instant::now();
+    let parsed = parse(input)?;
+    solve_parsed::<S>(&parsed, start.elapsed(), part)
+}
+
+/// Like [`solve`], for an input already parsed in `parse_time`, so that callers can reuse it.
+pub fn solve_parsed<S: Solution>(
+    parsed: &S::Input,
+    parse_time: Duration,
+    part: Option<u8>,
+) -> Result<Report> {
+    let mut parts = vec![];
+    if part.unwrap_or(1) == 1 {
+        parts.push(timed(1, || S::part1(parsed))?);
+    }
+    if part.unwrap_or(2) == 2 {
+        parts.push(timed(2, || S::part2(parsed))?);
+    }
+
+    Ok(Report {
+        day: S::DAY,
+        parse_time,
+        parts,
+    })
+}
+
+fn timed(part: u8, solve: impl FnOnce() -> Result<Answer>) -> Result<PartReport> {
+    let start = Instant::now();
+    let answer = solve()?;
+    Ok(PartReport {
+        part,
+        answer,
+        elapsed: start.elapsed(),
+    })
+}
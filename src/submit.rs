@@ -0,0 +1,373 @@
+//! Submitting answers, with a local log of past submissions so known-wrong
+//! answers and rate-limited submissions are refused without asking the server.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::client::Client;
+use crate::Answer;
+
+pub const SUBMISSIONS_FILE: &str = "submissions.toml";
+
+/// The submissions log, kept next to the cached inputs.
+pub fn submissions_path(inputs_dir: &Path) -> PathBuf {
+    inputs_dir.join(SUBMISSIONS_FILE)
+}
+
+/// What the server said about a submitted answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    TooRecent {
+        wait: Duration,
+    },
+    AlreadySolved,
+    Unknown(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// Reads the outcome from the `<article>` of the answer page.
+pub fn parse_response(html: &str) -> Outcome {
+    let text = article_text(html);
+
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Outcome::Wrong {
+            hint,
+            wait: parse_retry_wait(&text),
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::TooRecent {
+            wait: parse_left_to_wait(&text).unwrap_or(Duration::from_secs(60)),
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unknown(text)
+    }
+}
+
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// "You have 1m 23s left to wait."
+fn parse_left_to_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for part in text[start..end].split_whitespace() {
+        let (value, unit) = part.split_at(part.len() - 1);
+        let value: u64 = value.parse().ok()?;
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+// "Please wait one minute before trying again." / "please wait 5 minutes before trying again."
+fn parse_retry_wait(text: &str) -> Option<Duration> {
+    let lower = text.to_lowercase();
+    let start = lower.find("please wait ")? + "please wait ".len();
+    let mut words = lower[start..].split_whitespace();
+
+    let minutes = match words.next()? {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+    match words.next()? {
+        "minute" | "minutes" => Some(Duration::from_secs(minutes * 60)),
+        _ => None,
+    }
+}
+
+/// Past submissions of one part.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct PartLog {
+    correct: Option<String>,
+    wrong: Vec<String>,
+    too_high: Option<i64>,
+    too_low: Option<i64>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Submissions {
+    wait_until: Option<u64>,
+    parts: BTreeMap<(u8, u8), PartLog>,
+}
+
+impl Submissions {
+    /// Loads the log; a missing file means nothing was submitted yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Submissions::default());
+        }
+
+        let input = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Submissions::parse(&input).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        fs::write(path, self.to_toml()).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    fn parse(input: &str) -> Result<Self, String> {
+        let table: toml::Table = input.parse().map_err(|e| format!("{}", e))?;
+
+        let mut submissions = Submissions {
+            wait_until: table
+                .get("wait_until")
+                .and_then(|v| v.as_integer())
+                .map(|v| v as u64),
+            ..Default::default()
+        };
+
+        for (key, parts) in table.iter().filter(|(_, v)| v.is_table()) {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or(format!("Invalid day `{}`", key))?;
+
+            for (key, log) in parts.as_table().unwrap() {
+                let part = key
+                    .strip_prefix("part")
+                    .and_then(|p| p.parse().ok())
+                    .ok_or(format!("Invalid part `{}`", key))?;
+                let log = log.as_table().ok_or(format!("`{}` must be a table", key))?;
+
+                let log = PartLog {
+                    correct: log
+                        .get("correct")
+                        .and_then(|v| v.as_str())
+                        .map(String::from),
+                    wrong: log
+                        .get("wrong")
+                        .and_then(|v| v.as_array())
+                        .map(|a| {
+                            a.iter()
+                                .filter_map(|v| v.as_str())
+                                .map(String::from)
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    too_high: log.get("too_high").and_then(|v| v.as_integer()),
+                    too_low: log.get("too_low").and_then(|v| v.as_integer()),
+                };
+                submissions.parts.insert((day, part), log);
+            }
+        }
+
+        Ok(submissions)
+    }
+
+    fn to_toml(&self) -> String {
+        let mut table = toml::Table::new();
+        if let Some(wait_until) = self.wait_until {
+            table.insert("wait_until".to_string(), (wait_until as i64).into());
+        }
+
+        for ((day, part), log) in &self.parts {
+            let mut entry = toml::Table::new();
+            if let Some(correct) = &log.correct {
+                entry.insert("correct".to_string(), correct.clone().into());
+            }
+            if !log.wrong.is_empty() {
+                entry.insert("wrong".to_string(), log.wrong.clone().into());
+            }
+            if let Some(too_high) = log.too_high {
+                entry.insert("too_high".to_string(), too_high.into());
+            }
+            if let Some(too_low) = log.too_low {
+                entry.insert("too_low".to_string(), too_low.into());
+            }
+
+            table
+                .entry(format!("day{:02}", day))
+                .or_insert_with(|| toml::Table::new().into())
+                .as_table_mut()
+                .unwrap()
+                .insert(format!("part{}", part), entry.into());
+        }
+
+        table.to_string()
+    }
+
+    /// Refuses answers that cannot be right or that the server would not accept yet.
+    pub fn check(&self, day: u8, part: u8, answer: &Answer, now: u64) -> Result<(), String> {
+        if let Some(wait_until) = self.wait_until.filter(|w| *w > now) {
+            return Err(format!(
+                "Rate limited: wait {}s before submitting again",
+                wait_until - now
+            ));
+        }
+
+        let log = match self.parts.get(&(day, part)) {
+            Some(log) => log,
+            None => return Ok(()),
+        };
+        let answer_text = answer.to_string();
+
+        if let Some(correct) = &log.correct {
+            return Err(format!("Already solved, the answer was {}", correct));
+        }
+        if log.wrong.contains(&answer_text) {
+            return Err(format!("{} was already submitted and is wrong", answer));
+        }
+        if let Answer::Number(n) = answer {
+            if let Some(too_high) = log.too_high.filter(|h| n >= h) {
+                return Err(format!("{} is wrong, {} was already too high", n, too_high));
+            }
+            if let Some(too_low) = log.too_low.filter(|l| n <= l) {
+                return Err(format!("{} is wrong, {} was already too low", n, too_low));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, answer: &Answer, outcome: &Outcome, now: u64) {
+        let log = self.parts.entry((day, part)).or_default();
+
+        match outcome {
+            Outcome::Correct => log.correct = Some(answer.to_string()),
+            Outcome::Wrong { hint, wait } => {
+                log.wrong.push(answer.to_string());
+                if let Answer::Number(n) = answer {
+                    match hint {
+                        Some(Hint::TooHigh) => {
+                            log.too_high = Some(log.too_high.map_or(*n, |h| h.min(*n)))
+                        }
+                        Some(Hint::TooLow) => {
+                            log.too_low = Some(log.too_low.map_or(*n, |l| l.max(*n)))
+                        }
+                        None => {}
+                    }
+                }
+                if let Some(wait) = wait {
+                    self.wait_until = Some(now + wait.as_secs());
+                }
+            }
+            Outcome::TooRecent { wait } => self.wait_until = Some(now + wait.as_secs()),
+            Outcome::AlreadySolved | Outcome::Unknown(_) => {}
+        }
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Submits `answer` unless the log already knows it is wrong, then records the outcome.
+pub fn submit(
+    client: &Client,
+    log_path: &Path,
+    day: u8,
+    part: u8,
+    answer: &Answer,
+) -> Result<Outcome, String> {
+    let mut submissions = Submissions::load(log_path)?;
+    submissions.check(day, part, answer, now())?;
+
+    let html = client.post_answer(day, part, answer)?;
+    let outcome = parse_response(&html);
+
+    submissions.record(day, part, answer, &outcome, now());
+    submissions.save(log_path)?;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_responses() {
+        let html = "<main><article><p>That's not the right answer; your answer is too high. \
+                    Please wait one minute before trying again.</p></article></main>";
+        assert_eq!(
+            parse_response(html),
+            Outcome::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+
+        let html = "<article><p>You gave an answer too recently. You have 1m 23s left to wait.</p></article>";
+        assert_eq!(
+            parse_response(html),
+            Outcome::TooRecent {
+                wait: Duration::from_secs(83)
+            }
+        );
+
+        let html = "<article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article>";
+        assert_eq!(parse_response(html), Outcome::Correct);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let mut submissions = Submissions::default();
+        let wrong = Outcome::Wrong {
+            hint: Some(Hint::TooLow),
+            wait: Some(Duration::from_secs(60)),
+        };
+        submissions.record(1, 1, &Answer::Number(100), &wrong, 1000);
+
+        assert!(submissions.check(1, 1, &Answer::Number(200), 1030).is_err());
+        assert!(submissions.check(1, 1, &Answer::Number(200), 1060).is_ok());
+        assert!(submissions.check(1, 1, &Answer::Number(100), 1060).is_err());
+        assert!(submissions.check(1, 1, &Answer::Number(50), 1060).is_err());
+        assert!(submissions.check(1, 2, &Answer::Number(50), 1060).is_ok());
+
+        let saved = Submissions::parse(&submissions.to_toml()).unwrap();
+        assert_eq!(saved, submissions);
+    }
+}
@@ -0,0 +1,46 @@
+use std::fs;
+use std::path::Path;
+
+use advent_of_code_2022_rust::answers::Answers;
+use advent_of_code_2022_rust::DAYS;
+
+#[test]
+fn data_answers_match_known_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let answers = Answers::load(&root.join("answers.toml")).unwrap();
+
+    let mut failures = vec![];
+    for day in DAYS {
+        let path = root.join(format!("src/day{:02}/data.txt", day.number));
+        let input = fs::read_to_string(&path).unwrap();
+        // A freshly scaffolded day has no input yet.
+        if input.trim().is_empty() {
+            continue;
+        }
+
+        let report = match day.solve(&input, None) {
+            Ok(report) => report,
+            Err(e) => {
+                failures.push(format!("day {:02}: {}", day.number, e));
+                continue;
+            }
+        };
+
+        for part in &report.parts {
+            if answers.get(day.number, part.part).is_none() {
+                failures.push(format!(
+                    "day {:02} part {}: no known answer",
+                    day.number, part.part
+                ));
+            }
+        }
+        for (part, actual, expected) in answers.mismatches(&report) {
+            failures.push(format!(
+                "day {:02} part {}: expected {}, got {}",
+                day.number, part, expected, actual
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
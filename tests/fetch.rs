@@ -0,0 +1,58 @@
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use advent_of_code_2022_rust::client::{Client, Fetched};
+use common::StubServer;
+
+fn inputs_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn downloads_input_once_and_caches_it() {
+    let server = StubServer::start(|_| (200, "1000\n2000\n".to_string()));
+    let client = Client::new(&server.url, Some("secret".to_string()));
+    let dir = inputs_dir("cache");
+
+    let (path, fetched) = client.fetch_input(&dir, 1).unwrap();
+    assert_eq!(fetched, Fetched::Downloaded);
+    assert_eq!(path, dir.join("day01.txt"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+    let (_, fetched) = client.fetch_input(&dir, 1).unwrap();
+    assert_eq!(fetched, Fetched::Cached);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2022/day/1/input");
+    assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+    assert_eq!(requests[0].body, "");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn does_not_cache_failed_downloads() {
+    let server = StubServer::start(|_| (404, "Not found".to_string()));
+    let client = Client::new(&server.url, Some("secret".to_string()));
+    let dir = inputs_dir("missing");
+
+    let error = client.fetch_input(&dir, 25).unwrap_err();
+    assert!(error.contains("unlocked"), "{}", error);
+    assert!(!dir.join("day25.txt").exists());
+}
+
+#[test]
+fn requires_a_session_to_download() {
+    let server = StubServer::start(|_| (200, String::new()));
+    let client = Client::new(&server.url, None);
+
+    assert!(client.fetch_input(&inputs_dir("session"), 1).is_err());
+    assert!(server.requests().is_empty());
+}
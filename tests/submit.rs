@@ -0,0 +1,43 @@
+mod common;
+
+use std::env;
+use std::fs;
+
+use advent_of_code_2022_rust::client::Client;
+use advent_of_code_2022_rust::submit::{self, Hint, Outcome};
+use advent_of_code_2022_rust::Answer;
+use common::StubServer;
+
+const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+                        Please wait one minute before trying again.</p></article>";
+
+#[test]
+fn posts_answer_and_refuses_known_wrong_guesses() {
+    let server = StubServer::start(|_| (200, TOO_HIGH.to_string()));
+    let client = Client::new(&server.url, Some("secret".to_string()));
+    let log = env::temp_dir().join(format!("aoc-submissions-{}.toml", std::process::id()));
+    let _ = fs::remove_file(&log);
+
+    let outcome = submit::submit(&client, &log, 1, 2, &Answer::Number(300)).unwrap();
+    assert_eq!(
+        outcome,
+        Outcome::Wrong {
+            hint: Some(Hint::TooHigh),
+            wait: Some(std::time::Duration::from_secs(60))
+        }
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2022/day/1/answer");
+    assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+    assert_eq!(requests[0].body, "level=2&answer=300");
+
+    // Rate limited, and higher than an answer known to be too high: neither reaches the server.
+    assert!(submit::submit(&client, &log, 1, 2, &Answer::Number(100)).is_err());
+    assert!(submit::submit(&client, &log, 1, 2, &Answer::Number(400)).is_err());
+    assert_eq!(server.requests().len(), 1);
+
+    fs::remove_file(&log).unwrap();
+}